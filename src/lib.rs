@@ -1,6 +1,6 @@
-#![allow(non_snake_case)]
-
-pub mod man;
-pub mod pwd;
-pub mod winfetch;
-pub mod whoami;
+#![allow(non_snake_case)]
+
+pub mod man;
+pub mod pwd;
+pub mod winfetch;
+pub mod whoami;
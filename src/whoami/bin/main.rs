@@ -1,26 +1,31 @@
-use std::env;
-
-use linux_commands_on_windows::whoami;
-
-fn main() {
-    let mut commandline_arguments = env::args();
-
-    if let Some(argument) = commandline_arguments.nth(1) {
-        match &*argument {
-            "--help" => {
-                println!("{}", whoami::WHOAMI_HELP_MESSAGE);
-                return;
-            },
-            "--version" => {
-                println!("{}", whoami::WHOAMI_VERSION_MESSAGE);
-                return;
-            }
-            _ => {
-                println!("whoami: option `{}` is unknown", argument);
-                return;
-            }
-        }
-    }
-
-    println!("{}", whoami::GetUsername());
-}
+use std::env;
+
+use linux_commands_on_windows::whoami;
+
+fn main() {
+    let mut commandline_arguments = env::args();
+
+    if let Some(argument) = commandline_arguments.nth(1) {
+        match &*argument {
+            "--help" => {
+                println!("{}", whoami::WHOAMI_HELP_MESSAGE);
+                return;
+            },
+            "--version" => {
+                println!("{}", whoami::WHOAMI_VERSION_MESSAGE);
+                return;
+            }
+            _ => {
+                println!("whoami: option `{}` is unknown", argument);
+                return;
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    println!("{}", whoami::GetUsername());
+
+    // the user name is read with a win32 call; the crate only builds on other platforms so that `winfetch` can replay dumps
+    #[cfg(not(windows))]
+    println!("whoami: only supported on windows");
+}
@@ -1,8 +1,44 @@
-pub fn __InternalsToUnits(value: f64) -> String {
-    return if value > 1024f64.powi(4) {
-        format!("{:.2} TB", value / 1024.0f64.powi(4))
-    }
-    else {
-        format!("{:.2} GB", value / 1024.0f64.powi(3))
-    }
-}
+use chrono::{
+    DateTime,
+    FixedOffset,
+    NaiveDateTime,
+    TimeZone
+};
+
+use crate::winfetch::error::{
+    WinfetchError,
+    WinfetchResult
+};
+
+pub mod registry;
+pub mod wmi;
+
+#[cfg(not(windows))]
+pub fn __InternalsUnsupportedPlatform(source: &str) -> WinfetchError {
+    WinfetchError(format!("{} is only available on windows; use `--from-dump` to render a dump recorded on windows", source))
+}
+
+pub fn __InternalsToUnits(value: f64) -> String {
+    if value > 1024f64.powi(4) {
+        format!("{:.2} TB", value / 1024.0f64.powi(4))
+    }
+    else {
+        format!("{:.2} GB", value / 1024.0f64.powi(3))
+    }
+}
+
+// parses a cim datetime string, formatted as `yyyymmddHHMMSS.mmmmmmsUUU` with the offset in minutes
+pub fn __InternalsParseCIMDateTime(value: &str) -> WinfetchResult<DateTime<FixedOffset>> {
+    if value.len() != 25 || !value.is_ascii() {
+        return Err(WinfetchError(format!("invalid cim datetime value; expected 25 characters, got `{}`", value)));
+    }
+
+    let naive_date_time = NaiveDateTime::parse_from_str(&value[0..14], "%Y%m%d%H%M%S")
+        .map_err(|error| WinfetchError(format!("invalid cim datetime value `{}`; error: {}", value, error)))?;
+    let offset_minutes = value[21..25].parse::<i32>()
+        .map_err(|error| WinfetchError(format!("invalid cim datetime offset in `{}`; error: {}", value, error)))?;
+
+    FixedOffset::east_opt(offset_minutes * 60)
+        .and_then(|offset| offset.from_local_datetime(&naive_date_time).single())
+        .ok_or_else(|| WinfetchError(format!("invalid cim datetime offset in `{}`", value)))
+}
@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+
+#[cfg(windows)]
+use std::{
+    ffi::OsStr,
+    mem,
+    os::windows::ffi::OsStrExt,
+    ptr
+};
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::{
+        minwindef::{
+            DWORD,
+            HKEY
+        },
+        winerror::{
+            ERROR_FILE_NOT_FOUND,
+            ERROR_SUCCESS
+        }
+    },
+    um::{
+        winnt::{
+            KEY_READ,
+            PVOID,
+            REG_DWORD,
+            REG_EXPAND_SZ,
+            REG_MULTI_SZ,
+            REG_QWORD,
+            REG_SZ
+        },
+        winreg::{
+            HKEY_CURRENT_USER,
+            HKEY_LOCAL_MACHINE,
+            RegCloseKey as Win32_RegCloseKey,
+            RegEnumKeyExW as Win32_RegEnumKeyExW,
+            RegEnumValueW as Win32_RegEnumValueW,
+            RegGetValueW as Win32_RegGetValueW,
+            RegOpenKeyExW as Win32_RegOpenKeyExW,
+            RegQueryInfoKeyW as Win32_RegQueryInfoKeyW,
+            RRF_RT_REG_DWORD,
+            RRF_RT_REG_SZ
+        }
+    }
+};
+
+use crate::winfetch::{
+    dump::{
+        self,
+        RecordKey
+    },
+    error::WinfetchResult
+};
+
+#[cfg(windows)]
+use crate::winfetch::error::WinfetchError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegistryHive {
+    CurrentUser,
+    LocalMachine
+}
+
+impl RegistryHive {
+    #[cfg(windows)]
+    pub fn Handle(&self) -> HKEY {
+        match self {
+            Self::CurrentUser => HKEY_CURRENT_USER,
+            Self::LocalMachine => HKEY_LOCAL_MACHINE
+        }
+    }
+
+    pub fn Name(&self) -> &'static str {
+        match self {
+            Self::CurrentUser => "HKEY_CURRENT_USER",
+            Self::LocalMachine => "HKEY_LOCAL_MACHINE"
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum RegistryValue {
+    String(String),
+    MultiString(Vec<String>),
+    Dword(u32),
+    Qword(u64),
+    Binary(Vec<u8>)
+}
+
+impl RegistryValue {
+    #[cfg(windows)]
+    pub fn FromRawData(value_type: u32, data: &[u8]) -> Self {
+        let wide = || data.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect::<Vec<_>>();
+
+        match value_type {
+            REG_SZ | REG_EXPAND_SZ => Self::String(String::from_utf16_lossy(&wide()).trim_end_matches('\0').to_string()),
+            REG_MULTI_SZ => Self::MultiString(String::from_utf16_lossy(&wide())
+                .split('\0')
+                .filter(|string| !string.is_empty())
+                .map(String::from)
+                .collect()),
+            REG_DWORD if data.len() >= 4 => Self::Dword(u32::from_le_bytes([data[0], data[1], data[2], data[3]])),
+            REG_QWORD if data.len() >= 8 => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&data[..8]);
+
+                Self::Qword(u64::from_le_bytes(bytes))
+            },
+            _ => Self::Binary(data.to_vec())
+        }
+    }
+
+    pub fn AsString(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None
+        }
+    }
+
+    pub fn AsMultiString(&self) -> Option<&[String]> {
+        match self {
+            Self::MultiString(strings) => Some(strings),
+            _ => None
+        }
+    }
+
+    pub fn AsDword(&self) -> Option<u32> {
+        match self {
+            Self::Dword(dword) => Some(*dword),
+            _ => None
+        }
+    }
+}
+
+// a snapshot of a registry key, with all of its values and subkeys
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RegistryKey {
+    pub Values: BTreeMap<String, RegistryValue>,
+    pub SubKeys: BTreeMap<String, RegistryKey>
+}
+
+fn Intercept<T, F>(operation: &str, hive: RegistryHive, path: &str, name: Option<&str>, read: F) -> WinfetchResult<T>
+where
+    T: for<'de> Deserialize<'de> + Serialize,
+    F: FnOnce() -> WinfetchResult<T>
+{
+    let key = RecordKey::Registry {
+        Operation: operation.to_string(),
+        Path: match name {
+            Some(name) => format!("{}\\{}\\{}", hive.Name(), path, name),
+            None => format!("{}\\{}", hive.Name(), path)
+        }
+    };
+
+    dump::Intercept(key, read)
+}
+
+pub fn __InternalsRegistryGetString(hive: RegistryHive, path: &str, name: &str) -> WinfetchResult<String> {
+    Intercept("GetString", hive, path, Some(name), || GetStringLive(hive, path, name))
+}
+
+pub fn __InternalsRegistryGetDword(hive: RegistryHive, path: &str, name: &str) -> WinfetchResult<u32> {
+    Intercept("GetDword", hive, path, Some(name), || GetDwordLive(hive, path, name))
+}
+
+pub fn __InternalsRegistryKeyExists(hive: RegistryHive, path: &str) -> WinfetchResult<bool> {
+    Intercept("KeyExists", hive, path, None, || KeyExistsLive(hive, path))
+}
+
+// reads the key with all of its values and subkeys; `None` is returned if the key does not exist
+pub fn __InternalsRegistryReadKey(hive: RegistryHive, path: &str) -> WinfetchResult<Option<RegistryKey>> {
+    Intercept("ReadKey", hive, path, None, || ReadKeyLive(hive, path))
+}
+
+#[cfg(not(windows))]
+fn GetStringLive(_: RegistryHive, _: &str, _: &str) -> WinfetchResult<String> {
+    Err(super::__InternalsUnsupportedPlatform("the registry"))
+}
+
+#[cfg(not(windows))]
+fn GetDwordLive(_: RegistryHive, _: &str, _: &str) -> WinfetchResult<u32> {
+    Err(super::__InternalsUnsupportedPlatform("the registry"))
+}
+
+#[cfg(not(windows))]
+fn KeyExistsLive(_: RegistryHive, _: &str) -> WinfetchResult<bool> {
+    Err(super::__InternalsUnsupportedPlatform("the registry"))
+}
+
+#[cfg(not(windows))]
+fn ReadKeyLive(_: RegistryHive, _: &str) -> WinfetchResult<Option<RegistryKey>> {
+    Err(super::__InternalsUnsupportedPlatform("the registry"))
+}
+
+#[cfg(windows)]
+fn EncodeWide(string: &str) -> Vec<u16> {
+    AsRef::<OsStr>::as_ref(string)
+        .encode_wide()
+        .chain(Some(0))
+        .collect::<Vec<u16>>()
+}
+
+#[cfg(windows)]
+fn GetStringLive(hive: RegistryHive, path: &str, name: &str) -> WinfetchResult<String> {
+    let path_wide = EncodeWide(path);
+    let name_wide = EncodeWide(name);
+    let mut buffer_len: DWORD = 0;
+
+    unsafe {
+        match Win32_RegGetValueW(
+            hive.Handle(),
+            path_wide.as_ptr(),
+            name_wide.as_ptr(),
+            RRF_RT_REG_SZ,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut buffer_len
+        ) as DWORD {
+            ERROR_SUCCESS => (),
+            error_code => return Err(WinfetchError(
+                format!("could not get buffer length from `{}\\{}\\{}`; error code: {}", hive.Name(), path, name, error_code)
+            ))
+        }
+    }
+
+    let mut buffer = vec![0u16; buffer_len as usize / mem::size_of::<u16>()];
+
+    unsafe {
+        match Win32_RegGetValueW(
+            hive.Handle(),
+            path_wide.as_ptr(),
+            name_wide.as_ptr(),
+            RRF_RT_REG_SZ,
+            ptr::null_mut(),
+            buffer.as_mut_ptr() as PVOID,
+            &mut buffer_len
+        ) as DWORD {
+            ERROR_SUCCESS => (),
+            error_code => return Err(WinfetchError(
+                format!("could not get registry value from `{}\\{}\\{}`; error code: {}", hive.Name(), path, name, error_code)
+            ))
+        }
+    }
+
+    Ok(String::from_utf16_lossy(&buffer).replace('\0', ""))
+}
+
+#[cfg(windows)]
+fn GetDwordLive(hive: RegistryHive, path: &str, name: &str) -> WinfetchResult<u32> {
+    let path_wide = EncodeWide(path);
+    let name_wide = EncodeWide(name);
+    let mut value: DWORD = 0;
+
+    unsafe {
+        match Win32_RegGetValueW(
+            hive.Handle(),
+            path_wide.as_ptr(),
+            name_wide.as_ptr(),
+            RRF_RT_REG_DWORD,
+            ptr::null_mut(),
+            &mut value as *mut u32 as PVOID,
+            &mut (mem::size_of::<DWORD>() as u32)
+        ) as DWORD {
+            ERROR_SUCCESS => (),
+            error_code => return Err(WinfetchError(
+                format!("could not get value from `{}\\{}\\{}`; error code: {}", hive.Name(), path, name, error_code)
+            ))
+        }
+    }
+
+    Ok(value)
+}
+
+#[cfg(windows)]
+fn KeyExistsLive(hive: RegistryHive, path: &str) -> WinfetchResult<bool> {
+    let path_wide = EncodeWide(path);
+    let mut hkey: HKEY = ptr::null_mut();
+
+    unsafe {
+        match Win32_RegOpenKeyExW(hive.Handle(), path_wide.as_ptr(), 0, KEY_READ, &mut hkey) as DWORD {
+            ERROR_SUCCESS => {
+                Win32_RegCloseKey(hkey);
+                Ok(true)
+            },
+            ERROR_FILE_NOT_FOUND => Ok(false),
+            error_code => Err(WinfetchError(
+                format!("could not get registry key from `{}\\{}`; error code: {}", hive.Name(), path, error_code)
+            ))
+        }
+    }
+}
+
+#[cfg(windows)]
+unsafe fn ReadOpenedKey(hkey: HKEY, path: &str) -> WinfetchResult<RegistryKey> {
+    let mut sub_keys_count: DWORD = 0;
+    let mut max_sub_key_len: DWORD = 0;
+    let mut values_count: DWORD = 0;
+    let mut max_value_name_len: DWORD = 0;
+    let mut max_value_len: DWORD = 0;
+
+    match Win32_RegQueryInfoKeyW(
+        hkey,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+        &mut sub_keys_count,
+        &mut max_sub_key_len,
+        ptr::null_mut(),
+        &mut values_count,
+        &mut max_value_name_len,
+        &mut max_value_len,
+        ptr::null_mut(),
+        ptr::null_mut()
+    ) as DWORD {
+        ERROR_SUCCESS => (),
+        error_code => return Err(WinfetchError(format!("could not query registry key `{}`; error code: {}", path, error_code)))
+    }
+
+    let mut key = RegistryKey::default();
+
+    for index in 0..values_count {
+        let mut name_buffer = vec![0u16; max_value_name_len as usize + 1];
+        let mut name_len = name_buffer.len() as DWORD;
+        let mut data_buffer = vec![0u8; max_value_len as usize];
+        let mut data_len = data_buffer.len() as DWORD;
+        let mut value_type: DWORD = 0;
+
+        match Win32_RegEnumValueW(
+            hkey,
+            index,
+            name_buffer.as_mut_ptr(),
+            &mut name_len,
+            ptr::null_mut(),
+            &mut value_type,
+            data_buffer.as_mut_ptr(),
+            &mut data_len
+        ) as DWORD {
+            ERROR_SUCCESS => (),
+            error_code => return Err(WinfetchError(format!("could not enumerate values of registry key `{}`; error code: {}", path, error_code)))
+        }
+
+        key.Values.insert(
+            String::from_utf16_lossy(&name_buffer[..name_len as usize]),
+            RegistryValue::FromRawData(value_type, &data_buffer[..data_len as usize])
+        );
+    }
+
+    for index in 0..sub_keys_count {
+        let mut name_buffer = vec![0u16; max_sub_key_len as usize + 1];
+        let mut name_len = name_buffer.len() as DWORD;
+
+        match Win32_RegEnumKeyExW(
+            hkey,
+            index,
+            name_buffer.as_mut_ptr(),
+            &mut name_len,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut()
+        ) as DWORD {
+            ERROR_SUCCESS => (),
+            error_code => return Err(WinfetchError(format!("could not enumerate subkeys of registry key `{}`; error code: {}", path, error_code)))
+        }
+
+        let name = String::from_utf16_lossy(&name_buffer[..name_len as usize]);
+        let name_wide = EncodeWide(&name);
+        let mut sub_hkey: HKEY = ptr::null_mut();
+
+        match Win32_RegOpenKeyExW(hkey, name_wide.as_ptr(), 0, KEY_READ, &mut sub_hkey) as DWORD {
+            ERROR_SUCCESS => (),
+            error_code => return Err(WinfetchError(format!("could not open registry key `{}\\{}`; error code: {}", path, name, error_code)))
+        }
+
+        let sub_key = ReadOpenedKey(sub_hkey, &format!("{}\\{}", path, name));
+        Win32_RegCloseKey(sub_hkey);
+
+        key.SubKeys.insert(name, sub_key?);
+    }
+
+    Ok(key)
+}
+
+#[cfg(windows)]
+fn ReadKeyLive(hive: RegistryHive, path: &str) -> WinfetchResult<Option<RegistryKey>> {
+    let path_wide = EncodeWide(path);
+    let mut hkey: HKEY = ptr::null_mut();
+
+    unsafe {
+        match Win32_RegOpenKeyExW(hive.Handle(), path_wide.as_ptr(), 0, KEY_READ, &mut hkey) as DWORD {
+            ERROR_SUCCESS => (),
+            ERROR_FILE_NOT_FOUND => return Ok(None),
+            error_code => return Err(WinfetchError(
+                format!("could not get registry key from `{}\\{}`; error code: {}", hive.Name(), path, error_code)
+            ))
+        }
+
+        let key = ReadOpenedKey(hkey, &format!("{}\\{}", hive.Name(), path));
+        Win32_RegCloseKey(hkey);
+
+        key.map(Some)
+    }
+}
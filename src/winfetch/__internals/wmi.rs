@@ -0,0 +1,364 @@
+use std::collections::BTreeMap;
+
+#[cfg(windows)]
+use std::{
+    ffi::OsStr,
+    mem,
+    os::windows::ffi::OsStrExt,
+    ptr,
+    slice
+};
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::{
+        ntdef::NULL,
+        rpcdce::{
+            RPC_C_AUTHN_LEVEL_CALL,
+            RPC_C_AUTHN_WINNT,
+            RPC_C_AUTHZ_NONE,
+            RPC_C_IMP_LEVEL_IMPERSONATE
+        },
+        winerror::{
+            HRESULT,
+            S_FALSE,
+            S_OK
+        },
+        wtypes::{
+            VT_ARRAY,
+            VT_BOOL,
+            VT_BSTR,
+            VT_EMPTY,
+            VT_I2,
+            VT_I4,
+            VT_NULL,
+            VT_UI1
+        },
+        wtypesbase::CLSCTX_INPROC_SERVER
+    },
+    um::{
+        combaseapi::{
+            CoCreateInstance as Win32_CoCreateInstance,
+            CoSetProxyBlanket as Win32_CoSetProxyBlanket,
+            CoUninitialize as Win32_CoUninitialize
+        },
+        oaidl::VARIANT,
+        objbase::CoInitialize as Win32_CoInitialize,
+        objidl::EOAC_NONE,
+        oleauto::{
+            SafeArrayAccessData as Win32_SafeArrayAccessData,
+            SafeArrayGetLBound as Win32_SafeArrayGetLBound,
+            SafeArrayGetUBound as Win32_SafeArrayGetUBound,
+            SafeArrayUnaccessData as Win32_SafeArrayUnaccessData,
+            SysStringLen as Win32_SysStringLen,
+            VariantClear as Win32_VariantClear
+        },
+        wbemcli::{
+            CLSID_WbemLocator,
+            IEnumWbemClassObject,
+            IID_IWbemLocator,
+            IWbemClassObject,
+            IWbemLocator,
+            IWbemServices,
+            WBEM_FLAG_FORWARD_ONLY,
+            WBEM_FLAG_RETURN_IMMEDIATELY,
+            WBEM_INFINITE,
+            WBEM_S_FALSE,
+            WBEM_S_NO_ERROR
+        }
+    }
+};
+
+use crate::winfetch::{
+    dump::{
+        self,
+        RecordKey
+    },
+    error::{
+        WinfetchError,
+        WinfetchResult
+    }
+};
+
+pub const ROOT_CIMV2: &str = r"ROOT\CIMV2";
+pub const ROOT_WMI: &str = r"ROOT\WMI";
+pub const ROOT_STORAGE: &str = r"ROOT\Microsoft\Windows\Storage";
+pub const ROOT_DEVICE_GUARD: &str = r"ROOT\Microsoft\Windows\DeviceGuard";
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum WmiValue {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+    Array(Vec<WmiValue>)
+}
+
+impl WmiValue {
+    pub fn AsBoolean(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(boolean) => Some(*boolean),
+            _ => None
+        }
+    }
+
+    pub fn AsString(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None
+        }
+    }
+
+    // 64-bit integers are returned by wmi as strings, so these are parsed as well
+    pub fn AsInteger(&self) -> Option<i64> {
+        match self {
+            Self::Integer(integer) => Some(*integer),
+            Self::String(string) => string.trim().parse().ok(),
+            _ => None
+        }
+    }
+
+    pub fn AsArray(&self) -> Option<&[WmiValue]> {
+        match self {
+            Self::Array(array) => Some(array),
+            _ => None
+        }
+    }
+}
+
+pub type WmiRow = BTreeMap<String, WmiValue>;
+
+pub fn __InternalsWmiGet<'a>(row: &'a WmiRow, property: &str) -> WinfetchResult<&'a WmiValue> {
+    row.get(property).ok_or_else(|| WinfetchError(format!("could not get `{}` property of wbem class object", property)))
+}
+
+#[cfg(windows)]
+fn EncodeWide(string: &str) -> Vec<u16> {
+    AsRef::<OsStr>::as_ref(string)
+        .encode_wide()
+        .chain(Some(0))
+        .collect::<Vec<u16>>()
+}
+
+#[cfg(windows)]
+unsafe fn VariantToWmiValue(variant: &VARIANT) -> WinfetchResult<WmiValue> {
+    Ok(match variant.n1.n2().vt as u32 {
+        VT_EMPTY | VT_NULL => WmiValue::Null,
+        VT_BOOL => WmiValue::Boolean(*variant.n1.n2().n3.boolVal() != 0),
+        VT_UI1 => WmiValue::Integer(*variant.n1.n2().n3.bVal() as i64),
+        VT_I2 => WmiValue::Integer(*variant.n1.n2().n3.iVal() as i64),
+        VT_I4 => WmiValue::Integer(*variant.n1.n2().n3.lVal() as i64),
+        VT_BSTR => {
+            let bstr = *variant.n1.n2().n3.bstrVal();
+            let slice = slice::from_raw_parts(bstr, Win32_SysStringLen(bstr) as usize);
+
+            WmiValue::String(String::from_utf16_lossy(slice))
+        },
+        variant_type if variant_type & VT_ARRAY != 0 => {
+            let array = *variant.n1.n2().n3.parray();
+            let mut lower_bound = 0;
+            let mut upper_bound = -1;
+
+            Win32_SafeArrayGetLBound(array, 1, &mut lower_bound);
+            Win32_SafeArrayGetUBound(array, 1, &mut upper_bound);
+
+            let length = (upper_bound - lower_bound + 1).max(0) as usize;
+            let mut data = ptr::null_mut();
+
+            if Win32_SafeArrayAccessData(array, &mut data) != S_OK {
+                return Err(WinfetchError(String::from("could not access the data of a safe array")));
+            }
+
+            let values = match variant_type & !VT_ARRAY {
+                VT_UI1 => slice::from_raw_parts(data as *const u8, length).iter().map(|&value| WmiValue::Integer(value as i64)).collect(),
+                VT_I2 => slice::from_raw_parts(data as *const i16, length).iter().map(|&value| WmiValue::Integer(value as i64)).collect(),
+                VT_I4 => slice::from_raw_parts(data as *const i32, length).iter().map(|&value| WmiValue::Integer(value as i64)).collect(),
+                element_type => {
+                    Win32_SafeArrayUnaccessData(array);
+
+                    return Err(WinfetchError(format!("unsupported array element type: {}", element_type)));
+                }
+            };
+
+            Win32_SafeArrayUnaccessData(array);
+
+            WmiValue::Array(values)
+        },
+        variant_type => return Err(WinfetchError(format!("unsupported variant type: {}", variant_type)))
+    })
+}
+
+#[cfg(windows)]
+unsafe fn QueryWbemServices(wbem_service: &IWbemServices, query: &str, properties: &[&str]) -> WinfetchResult<Vec<WmiRow>> {
+    let mut wql = EncodeWide("WQL");
+    let mut query_wide = EncodeWide(query);
+    let mut enum_wbem_class_object_null = NULL as *mut IEnumWbemClassObject;
+
+    match wbem_service.ExecQuery(
+        wql.as_mut_ptr(),
+        query_wide.as_mut_ptr(),
+        (WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY) as i32,
+        ptr::null_mut(),
+        &mut enum_wbem_class_object_null
+    ) {
+        S_OK => (),
+        error_code => return Err(WinfetchError(format!("failed to execute query `{}`; error code: {}", query, error_code)))
+    }
+
+    let mut hresult: HRESULT = WBEM_S_NO_ERROR as i32;
+    let mut rows = Vec::new();
+
+    while hresult == WBEM_S_NO_ERROR as i32 {
+        let mut wbem_class_object_null = NULL as *mut IWbemClassObject;
+        let mut return_value = 0;
+
+        hresult = (*enum_wbem_class_object_null).Next(
+            WBEM_INFINITE as i32,
+            1,
+            &mut wbem_class_object_null,
+            &mut return_value
+        );
+
+        if hresult == WBEM_S_FALSE as i32 || wbem_class_object_null.is_null() {
+            break;
+        }
+
+        let mut row = WmiRow::new();
+
+        for &property in properties {
+            let property_name = EncodeWide(property);
+            let mut variant = mem::zeroed::<VARIANT>();
+
+            match (*wbem_class_object_null).Get(
+                property_name.as_ptr(),
+                0,
+                &mut variant,
+                ptr::null_mut(),
+                ptr::null_mut()
+            ) {
+                S_OK => (),
+                error_code => {
+                    (*wbem_class_object_null).Release();
+                    (*enum_wbem_class_object_null).Release();
+                    return Err(WinfetchError(format!("could not get `{}` property of wbem class object; error code: {}", property, error_code)))
+                }
+            }
+
+            let value = VariantToWmiValue(&variant);
+            Win32_VariantClear(&mut variant);
+
+            row.insert(property.to_string(), value?);
+        }
+
+        (*wbem_class_object_null).Release();
+        rows.push(row);
+    }
+
+    (*enum_wbem_class_object_null).Release();
+
+    Ok(rows)
+}
+
+pub fn __InternalsWmiQuery(namespace: &str, query: &str, properties: &[&str]) -> WinfetchResult<Vec<WmiRow>> {
+    let key = RecordKey::Wmi {
+        Namespace: namespace.to_string(),
+        Query: query.to_string()
+    };
+
+    dump::Intercept(key, || QueryLive(namespace, query, properties))
+}
+
+#[cfg(not(windows))]
+fn QueryLive(_: &str, _: &str, _: &[&str]) -> WinfetchResult<Vec<WmiRow>> {
+    Err(super::__InternalsUnsupportedPlatform("wmi"))
+}
+
+#[cfg(windows)]
+fn QueryLive(namespace: &str, query: &str, properties: &[&str]) -> WinfetchResult<Vec<WmiRow>> {
+    let mut wbem_locator_c_void = NULL;
+
+    unsafe {
+        match Win32_CoInitialize(
+            NULL
+        ) {
+            S_OK | S_FALSE => (),
+            error_code => return Err(WinfetchError(format!("failed to initialize com library; error code: {}", error_code)))
+        }
+
+        match Win32_CoCreateInstance(
+            &CLSID_WbemLocator,
+            ptr::null_mut(),
+            CLSCTX_INPROC_SERVER,
+            &IID_IWbemLocator,
+            &mut wbem_locator_c_void
+        ) {
+            S_OK => (),
+            error_code => {
+                Win32_CoUninitialize();
+                return Err(WinfetchError(format!("failed to create wbem locator; error code: {}", error_code)))
+            }
+        }
+    }
+
+    let wbem_locator = wbem_locator_c_void as *mut IWbemLocator;
+    let mut namespace_wide = EncodeWide(namespace);
+    let mut wbem_service = ptr::null_mut::<IWbemServices>();
+
+    unsafe {
+        match (*wbem_locator).ConnectServer(
+            namespace_wide.as_mut_ptr(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            0,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut wbem_service
+        ) {
+            S_OK => (),
+            error_code => {
+                (*wbem_locator).Release();
+                Win32_CoUninitialize();
+                return Err(WinfetchError(format!("failed to connect to wbem namespace `{}`; error code: {}", namespace, error_code)))
+            }
+        }
+
+        match Win32_CoSetProxyBlanket(
+            wbem_service as _,
+            RPC_C_AUTHN_WINNT,
+            RPC_C_AUTHZ_NONE,
+            ptr::null_mut(),
+            RPC_C_AUTHN_LEVEL_CALL,
+            RPC_C_IMP_LEVEL_IMPERSONATE,
+            NULL,
+            EOAC_NONE
+        ) {
+            S_OK => (),
+            error_code => {
+                (*wbem_service).Release();
+                (*wbem_locator).Release();
+                Win32_CoUninitialize();
+                return Err(WinfetchError(format!("failed to set proxy; error code: {}", error_code)))
+            }
+        }
+    }
+
+    let rows = unsafe {
+        QueryWbemServices(&*wbem_service, query, properties)
+    };
+
+    unsafe {
+        (*wbem_service).Release();
+        (*wbem_locator).Release();
+
+        Win32_CoUninitialize();
+    }
+
+    rows
+}
@@ -1,73 +1,346 @@
-#![feature(iter_advance_by)]
+#![allow(non_snake_case)]
+
+use std::{
+    env,
+    path::{
+        Path,
+        PathBuf
+    },
+    time::Duration
+};
 
 use linux_commands_on_windows::winfetch::{
-    model::{
-        graphicscard,
-        hostsystem,
-        memory,
-        motherboard,
-        names,
-        os,
-        processor,
-        processorusage,
-        screenres,
-        storage,
-        uptime,
-        winntkernel,
+    self,
+    catalog,
+    config::Config,
+    diff,
+    dump::{
+        self,
+        RawDump
+    },
+    image::{
+        EncodedImage,
+        Image,
+        ImageProtocol
+    },
+    layout::{
+        self,
+        Overflow,
+        ReportFormat
     },
-    utils
+    module::Module,
+    report::SystemReport,
+    utils,
+    watch,
+    Options
 };
 
+// `winfetch diff <old.json> <new.json> [--json]`
+fn Diff(arguments: &[String]) {
+    let (paths, options): (Vec<_>, Vec<_>) = arguments.iter().partition(|argument| !argument.starts_with("--"));
+    let mut json_mode = false;
+
+    for option in options {
+        match &**option {
+            "--json" => json_mode = true,
+            _ => {
+                println!("winfetch: option `{}` is unknown", option);
+                return;
+            }
+        }
+    }
+
+    let (old, new) = match &paths[..] {
+        [old, new] => match (diff::LoadReport(Path::new(old)), diff::LoadReport(Path::new(new))) {
+            (Ok(old), Ok(new)) => (old, new),
+            (Err(error), _) | (_, Err(error)) => {
+                println!("winfetch: {}", error);
+                return;
+            }
+        },
+        _ => {
+            println!("winfetch: `diff` requires two reports saved with `--json`");
+            return;
+        }
+    };
+
+    let changes = diff::DiffReports(&old, &new);
+
+    if json_mode {
+        println!("{}", diff::RenderDiffJson(&changes));
+        return;
+    }
+
+    if changes.is_empty() {
+        println!("the reports are identical");
+        return;
+    }
+
+    diff::RenderDiff(&changes).into_iter().for_each(|string| {
+        println!("{}", string)
+    });
+}
+
+// the options of the default command; `--help` and `--version` are handled before the others are looked at
+struct Arguments {
+    ConfigPath: Option<PathBuf>,
+    Only: Option<Vec<Module>>,
+    Hide: Vec<Module>,
+    Logo: Option<String>,
+    NoLogo: bool,
+    StdoutMode: bool,
+    JsonMode: bool,
+    ImagePath: Option<PathBuf>,
+    WatchInterval: Option<Duration>,
+    ReportFormat: Option<ReportFormat>,
+    DumpPath: Option<PathBuf>,
+    FromDumpPath: Option<PathBuf>
+}
+
+// `cpu,memory` becomes the two modules, in that order
+fn ParseModules(list: &str) -> Result<Vec<Module>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Module::FromName(name).ok_or_else(|| format!("module `{}` is unknown", name)))
+        .collect()
+}
+
+fn ParseArguments(arguments: Vec<String>) -> Result<Arguments, String> {
+    let mut parsed = Arguments {
+        ConfigPath: None,
+        Only: None,
+        Hide: Vec::new(),
+        Logo: None,
+        NoLogo: false,
+        StdoutMode: false,
+        JsonMode: false,
+        ImagePath: None,
+        WatchInterval: None,
+        ReportFormat: None,
+        DumpPath: None,
+        FromDumpPath: None
+    };
+    let mut arguments = arguments.into_iter().peekable();
+
+    while let Some(argument) = arguments.next() {
+        let mut value = |name: &str, expected: &str| arguments.next().ok_or_else(|| format!("option `{}` requires {}", name, expected));
+
+        match &*argument {
+            "--config" => parsed.ConfigPath = Some(PathBuf::from(value("--config", "a path")?)),
+            "--only" => parsed.Only = Some(ParseModules(&value("--only", "a list of modules")?)?),
+            "--hide" => parsed.Hide.extend(ParseModules(&value("--hide", "a list of modules")?)?),
+            "--logo" => {
+                let name = value("--logo", "a logo name")?;
+
+                if utils::GetLogo(&name).is_none() {
+                    return Err(format!("logo `{}` is unknown; expected one of {}", name, utils::LOGO_NAMES.join(", ")));
+                }
+
+                parsed.Logo = Some(name);
+            },
+            "--no-logo" => parsed.NoLogo = true,
+            "--stdout" => parsed.StdoutMode = true,
+            "--json" => parsed.JsonMode = true,
+            "--format" => {
+                let name = value("--format", "a format")?;
+
+                parsed.ReportFormat = Some(ReportFormat::FromName(&name)
+                    .ok_or_else(|| format!("format `{}` is unknown; expected `markdown` or `html`", name))?);
+            },
+            "--image" => parsed.ImagePath = Some(PathBuf::from(value("--image", "a path")?)),
+            "--dump" => parsed.DumpPath = Some(PathBuf::from(value("--dump", "a path")?)),
+            "--from-dump" => parsed.FromDumpPath = Some(PathBuf::from(value("--from-dump", "a path")?)),
+            // the interval, in seconds, is optional
+            "--watch" => parsed.WatchInterval = Some(match arguments.next_if(|interval| !interval.starts_with("--")) {
+                Some(interval) => match interval.parse::<f64>().ok().filter(|&seconds| seconds > 0.0) {
+                    Some(seconds) => Duration::from_secs_f64(seconds),
+                    None => return Err(format!("invalid interval `{}` for option `--watch`", interval))
+                },
+                None => watch::DEFAULT_WATCH_INTERVAL
+            }),
+            _ => return Err(format!("option `{}` is unknown; see `winfetch --help`", argument))
+        }
+    }
+
+    if parsed.NoLogo && (parsed.Logo.is_some() || parsed.ImagePath.is_some()) {
+        return Err(String::from("option `--no-logo` cannot be combined with `--logo` or `--image`"));
+    }
+
+    if parsed.DumpPath.is_some() && parsed.FromDumpPath.is_some() {
+        return Err(String::from("option `--dump` cannot be combined with `--from-dump`"));
+    }
+
+    Ok(parsed)
+}
+
 fn main() {
-    let names = names::Names::GetNames();
-
-    let mut operating_system = os::OS::GetOperatingSystemVersion().unwrap();
-    operating_system.GetOperatingSystemArchitecture().unwrap();
-
-    let host_system = hostsystem::HostSystem::GetHostSystemInformation().unwrap();
-    let windows_nt_kernel = winntkernel::WindowsNTKernel::GetCurrentWindowsNTKernelVersion().unwrap();
-    let motherboard = motherboard::Motherboard::GetMotherboard().unwrap();
-    let uptime = uptime::SystemUptime::GetSystemUptime().unwrap();
-    let screen_resolutions = screenres::ScreenResolution::GetScreenResolution().unwrap();
-    let processor = processor::Processor::GetProcessor().unwrap();
-    let graphics_card = graphicscard::GraphicsCard::GetGraphicsCards().unwrap();
-    let mut processor_usage = processorusage::ProcessorUsage::GetProcessorLoadPercentage().unwrap();
-    processor_usage.GetProcessesCount().unwrap();
-    let memory = memory::Memory::GetMemoryStatistics().unwrap();
-    let mut drives = storage::Storage::GetStorageStatistics().unwrap();
-
-    let mut lines = utils::GetWindowsASCIIArt().lines().map(|refstr| refstr.to_string()).collect::<Vec<String>>();
-    lines[0].push_str(&format!("  {}", names));
-    lines[1].push_str(&format!("{}0m  {}", utils::ANSI_ESCAPE_SEQUENCE, String::from("-").repeat(
-        format!("{}@{}", names.UserName.clone().into_string().unwrap().to_ascii_lowercase(), names.ComputerName.clone().into_string().unwrap().to_ascii_lowercase()).len()).as_str()));
-    lines[2].push_str(&format!("  OS{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, operating_system));
-    lines[3].push_str(&format!("  Host System{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, host_system));
-    lines[4].push_str(&format!("  Kernel{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, windows_nt_kernel));
-    lines[5].push_str(&format!("  Motherboard{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, motherboard));
-    lines[6].push_str(&format!("  System Uptime{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, uptime));
-    lines[7].push_str(&format!("  Screen Resolutions(s){}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, screen_resolutions));
-    lines[8].push_str(&format!("                                     Processor{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, processor));
-    lines[9].push_str(&format!("  Graphics Card(s){}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, graphics_card));
-    lines[10].push_str(&format!("  Memory{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, memory));
-
-    let mut iteration = 0;
-
-    for (index, _) in lines.clone().iter().enumerate() {
-        if drives.Drives.get(index - iteration).is_some() {
-            lines[11 + index].push_str(&format!(" {}", drives.Drives.remove(0)))
+    let arguments = env::args().skip(1).collect::<Vec<_>>();
+
+    match arguments.first().map(String::as_str) {
+        Some("diff") => {
+            Diff(&arguments[1..]);
+            return;
+        },
+        _ if arguments.iter().any(|argument| argument == "--help") => {
+            println!("{}", winfetch::WINFETCH_HELP_MESSAGE);
+            return;
+        },
+        _ if arguments.iter().any(|argument| argument == "--version") => {
+            println!("{}", winfetch::WINFETCH_VERSION_MESSAGE);
+            return;
+        },
+        _ => ()
+    }
+
+    let arguments = match ParseArguments(arguments) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            println!("winfetch: {}", error);
+            return;
+        }
+    };
+
+    let config = match &arguments.ConfigPath {
+        Some(path) => Config::LoadConfigFrom(path),
+        None => Config::LoadConfig()
+    };
+    let mut config = match config {
+        Ok(config) => config,
+        Err(error) => {
+            println!("winfetch: {}", error);
+            return;
+        }
+    };
+
+    if let Some(modules) = &arguments.Only {
+        config.Modules = modules.clone();
+    }
+
+    config.Modules.retain(|module| !arguments.Hide.contains(module));
+
+    let logo = match &arguments.Logo {
+        _ if arguments.NoLogo => None,
+        Some(name) => utils::GetLogo(name),
+        None => Some(utils::GetWindowsASCIIArt())
+    };
+
+    let options = Options {
+        Modules: config.Modules.clone(),
+        SampleInterval: config.ProcessorUsage.SampleInterval(),
+        TopProcesses: config.Top.Count,
+        ProcessSort: config.Top.Sort,
+        StorageFilter: config.Storage.Filter(),
+        PhysicalDisks: config.Storage.PhysicalDisks,
+        CustomModules: config.Custom.clone()
+    };
+
+    if let Some(path) = &arguments.FromDumpPath {
+        match RawDump::LoadDump(path) {
+            Ok(raw_dump) => dump::StartReplaying(raw_dump),
+            Err(error) => {
+                println!("winfetch: {}", error);
+                return;
+            }
         }
+    }
+    else if arguments.DumpPath.is_some() {
+        dump::StartRecording();
+    }
 
-        iteration += 1;
+    // after the dump is loaded, so that a replayed report is labeled in the language of the machine it was recorded on
+    catalog::SetLanguage(&config.Language);
+
+    let mut report = match winfetch::Collect(&options) {
+        Ok(report) => report,
+        Err(error) => {
+            println!("winfetch: {}", error);
+            return;
+        }
+    };
+
+    // only the first collection is recorded; watch mode refreshes from the system again
+    if let Some(path) = &arguments.DumpPath {
+        if let Err(error) = dump::FinishRecording().SaveDump(path) {
+            println!("winfetch: {}", error);
+            return;
+        }
+    }
+
+    if arguments.JsonMode {
+        println!("{}", layout::RenderJson(&report));
+        return;
+    }
+
+    let lines = |report: &SystemReport, split_values: bool| Module::GetModulesInfoLines(&config.Modules, report, &config.Templates, config.Title.Case, split_values);
+
+    match arguments.ReportFormat {
+        Some(ReportFormat::Markdown) => {
+            print!("{}", layout::RenderMarkdown(&lines(&report, false)));
+            return;
+        },
+        Some(ReportFormat::Html) => {
+            print!("{}", layout::RenderHtml(logo.as_deref().unwrap_or_default(), &lines(&report, false)));
+            return;
+        },
+        None => ()
+    }
+
+    let image_path = arguments.ImagePath.clone().or_else(|| config.Image.Path.clone());
+    let image_protocol = config.Image.Protocol.or_else(ImageProtocol::DetectImageProtocol);
+
+    // fall back to the ascii art when the terminal is not known to support any image protocol
+    let encoded_image = match (image_path, image_protocol) {
+        (Some(path), Some(protocol)) if !arguments.StdoutMode && !arguments.NoLogo => match Image::LoadPNG(&path) {
+            Ok(image) => Some(EncodedImage::EncodeImage(&image, protocol, config.Image.Columns, config.Image.CellWidth, config.Image.CellHeight)),
+            Err(error) => {
+                println!("winfetch: {}", error);
+                return;
+            }
+        },
+        _ => None
+    };
+
+    let render = |report: &SystemReport| {
+        if arguments.StdoutMode {
+            return layout::RenderStdout(&lines(report, false));
+        }
+
+        // the width is read on every render so that resizing the terminal is followed in watch mode
+        let lines = lines(report, config.Layout.Overflow == Overflow::Split);
+        let offset = match (&encoded_image, &logo) {
+            (Some(encoded_image), _) => encoded_image.Columns + 2,
+            (None, Some(logo)) => layout::LogoWidth(logo) + 2,
+            (None, None) => 0
+        };
+        let lines = match layout::GetTerminalWidth() {
+            Some(width) => layout::FitInfoLines(lines, width.saturating_sub(offset), config.Layout.Overflow),
+            None => lines
+        };
+
+        match (&encoded_image, &logo) {
+            (Some(encoded_image), _) => layout::RenderWithImage(encoded_image, &lines),
+            (None, Some(logo)) => layout::RenderWithLogo(logo, &lines),
+            (None, None) => layout::RenderWithoutLogo(&lines)
+        }
+    };
+
+    if let Some(interval) = arguments.WatchInterval {
+        if let Err(error) = watch::Watch(&mut report, &options, interval, render) {
+            println!("winfetch: {}", error);
+        }
+        return;
     }
 
-    if !drives.Drives.is_empty() {
-        drives.Drives.into_iter().for_each(|drive| {
-            println!("                                  {}", drive)
+    if arguments.StdoutMode {
+        render(&report).into_iter().for_each(|string| {
+            println!("{}", string)
         });
+        return;
     }
 
     println!();
-    lines.into_iter().for_each(|string| {
+    render(&report).into_iter().for_each(|string| {
         println!("{}", string)
     });
     println!();
@@ -0,0 +1,176 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    sync::RwLock
+};
+
+use serde::Deserialize;
+
+use crate::winfetch::{
+    error::{
+        WinfetchError,
+        WinfetchResult
+    },
+    model::locale
+};
+
+// the languages with a catalog; english is the fallback for the others and for missing messages
+pub const LANGUAGES: &[&str] = &["en", "de", "es", "fr", "ja", "zh"];
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+fn EmbeddedCatalog(language: &str) -> Option<&'static str> {
+    match language {
+        "en" => Some(include_str!("catalogs/en.toml")),
+        "de" => Some(include_str!("catalogs/de.toml")),
+        "es" => Some(include_str!("catalogs/es.toml")),
+        "fr" => Some(include_str!("catalogs/fr.toml")),
+        "ja" => Some(include_str!("catalogs/ja.toml")),
+        "zh" => Some(include_str!("catalogs/zh.toml")),
+        _ => None
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PluralCategory {
+    One,
+    Other
+}
+
+impl PluralCategory {
+    // the cardinal rules of the catalog languages; japanese and chinese do not inflect for number
+    pub fn FromCount(language: &str, count: u64) -> Self {
+        match language {
+            "ja" | "zh" => Self::Other,
+            "fr" if count <= 1 => Self::One,
+            "fr" => Self::Other,
+            _ if count == 1 => Self::One,
+            _ => Self::Other
+        }
+    }
+}
+
+// `{count}` is replaced with the number; `one` falls back to `other`
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PluralForms {
+    #[serde(rename = "one")]
+    pub One: Option<String>,
+    #[serde(rename = "other")]
+    pub Other: String
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Catalog {
+    #[serde(rename = "messages", default)]
+    pub Messages: BTreeMap<String, String>,
+    #[serde(rename = "plurals", default)]
+    pub Plurals: BTreeMap<String, PluralForms>
+}
+
+impl Catalog {
+    pub fn ParseCatalog(content: &str) -> WinfetchResult<Self> {
+        toml::from_str(content).map_err(|error| WinfetchError(format!("could not parse catalog; error: {}", error)))
+    }
+
+    pub fn LoadCatalog(language: &str) -> WinfetchResult<Self> {
+        let content = EmbeddedCatalog(language).ok_or_else(|| WinfetchError(format!("there is no catalog for language `{}`", language)))?;
+
+        Self::ParseCatalog(content).map_err(|WinfetchError(error)| WinfetchError(format!("{} `{}`", error, language)))
+    }
+}
+
+// `de-AT`, `de_DE.UTF-8` and `zh-Hans-CN` select the `de` and `zh` catalogs
+pub fn ResolveLanguage(language: &str) -> Option<&'static str> {
+    let primary = language.split(['-', '_', '.', '@']).next()?.to_ascii_lowercase();
+
+    LANGUAGES.iter().copied().find(|&known| known == primary)
+}
+
+// the messages of one language, with the english ones behind them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Messages {
+    pub Language: &'static str,
+    pub Catalog: Catalog,
+    pub Fallback: Catalog
+}
+
+impl Messages {
+    // unknown languages fall back to english
+    pub fn ForLanguage(language: &str) -> Self {
+        let language = ResolveLanguage(language).unwrap_or(DEFAULT_LANGUAGE);
+
+        Self {
+            Language: language,
+            Catalog: Catalog::LoadCatalog(language).unwrap_or_default(),
+            Fallback: Catalog::LoadCatalog(DEFAULT_LANGUAGE).unwrap_or_default()
+        }
+    }
+
+    // a message missing from both catalogs is shown as its id, so that the gap is noticed
+    pub fn Message(&self, id: &str) -> String {
+        self.Catalog.Messages
+            .get(id)
+            .or_else(|| self.Fallback.Messages.get(id))
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    // `{name}` in the message is replaced with the value of that name
+    pub fn Format(&self, id: &str, arguments: &[(&str, &str)]) -> String {
+        arguments.iter().fold(self.Message(id), |message, (name, value)| message.replace(&format!("{{{}}}", name), value))
+    }
+
+    pub fn Plural(&self, id: &str, count: u64) -> String {
+        let (forms, language) = match (self.Catalog.Plurals.get(id), self.Fallback.Plurals.get(id)) {
+            (Some(forms), _) => (forms, self.Language),
+            (None, Some(forms)) => (forms, DEFAULT_LANGUAGE),
+            (None, None) => return format!("{} {}", count, id)
+        };
+
+        let form = match PluralCategory::FromCount(language, count) {
+            PluralCategory::One => forms.One.as_ref().unwrap_or(&forms.Other),
+            PluralCategory::Other => &forms.Other
+        };
+
+        form.replace("{count}", &count.to_string())
+    }
+}
+
+static ACTIVE_MESSAGES: RwLock<Option<Messages>> = RwLock::new(None);
+
+// the display language of the user, or of the machine the replayed dump was recorded on, then the posix locale variables
+pub fn DetectLanguage() -> Option<String> {
+    locale::GetDisplayLanguage()
+        .or_else(|| ["LC_ALL", "LC_MESSAGES", "LANG"].iter().find_map(|variable| env::var(variable).ok().filter(|value| !value.is_empty())))
+}
+
+// selects the catalog used by `Message` and `Plural`; `auto` detects the language of the user
+pub fn SetLanguage(language: &str) {
+    let language = match language {
+        "auto" => DetectLanguage().unwrap_or_else(|| DEFAULT_LANGUAGE.to_string()),
+        _ => language.to_string()
+    };
+
+    *ACTIVE_MESSAGES.write().unwrap() = Some(Messages::ForLanguage(&language));
+}
+
+fn WithMessages<R, F: FnOnce(&Messages) -> R>(function: F) -> R {
+    if let Some(messages) = &*ACTIVE_MESSAGES.read().unwrap() {
+        return function(messages);
+    }
+
+    let mut active_messages = ACTIVE_MESSAGES.write().unwrap();
+
+    function(active_messages.get_or_insert_with(|| Messages::ForLanguage(DEFAULT_LANGUAGE)))
+}
+
+pub fn Message(id: &str) -> String {
+    WithMessages(|messages| messages.Message(id))
+}
+
+pub fn Plural(id: &str, count: u64) -> String {
+    WithMessages(|messages| messages.Plural(id, count))
+}
+
+pub fn Format(id: &str, arguments: &[(&str, &str)]) -> String {
+    WithMessages(|messages| messages.Format(id, arguments))
+}
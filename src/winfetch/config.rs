@@ -0,0 +1,277 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fs,
+    path::{
+        Path,
+        PathBuf
+    },
+    time::Duration
+};
+
+use serde::{
+    de::Error,
+    Deserialize,
+    Deserializer
+};
+
+use crate::winfetch::{
+    catalog,
+    error::{
+        WinfetchError,
+        WinfetchResult
+    },
+    image::ImageProtocol,
+    layout::Overflow,
+    model::{
+        custom::CustomModule,
+        names::NameCase,
+        processes::{
+            self,
+            ProcessSort
+        },
+        processorusage,
+        storage::{
+            DriveType,
+            StorageFilter
+        }
+    },
+    module::Module,
+    template::Template
+};
+
+#[derive(Deserialize)]
+pub struct Config {
+    // a language code like `de` or `de-AT`, or `auto` for the display language of the user
+    #[serde(rename = "language", default = "DefaultLanguage")]
+    pub Language: String,
+    #[serde(rename = "modules", default = "Module::DefaultModules")]
+    pub Modules: Vec<Module>,
+    #[serde(rename = "title", default)]
+    pub Title: TitleConfig,
+    #[serde(rename = "cpu_usage", default)]
+    pub ProcessorUsage: ProcessorUsageConfig,
+    #[serde(rename = "top", default)]
+    pub Top: TopConfig,
+    #[serde(rename = "storage", default)]
+    pub Storage: StorageConfig,
+    #[serde(rename = "layout", default)]
+    pub Layout: LayoutConfig,
+    #[serde(rename = "image", default)]
+    pub Image: ImageConfig,
+    #[serde(rename = "templates", default, deserialize_with = "DeserializeTemplates")]
+    pub Templates: BTreeMap<Module, Template>,
+    #[serde(rename = "custom", default)]
+    pub Custom: Vec<CustomModule>
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct TitleConfig {
+    #[serde(rename = "case")]
+    pub Case: NameCase
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    #[serde(rename = "overflow")]
+    pub Overflow: Overflow
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct ImageConfig {
+    #[serde(rename = "path")]
+    pub Path: Option<PathBuf>,
+    #[serde(rename = "protocol")]
+    pub Protocol: Option<ImageProtocol>,
+    #[serde(rename = "columns")]
+    pub Columns: usize,
+    #[serde(rename = "cell_width")]
+    pub CellWidth: usize,
+    #[serde(rename = "cell_height")]
+    pub CellHeight: usize
+}
+
+impl ImageConfig {
+    // the image is sized and laid out in whole cells, so none of the sizes can be zero
+    pub fn Validate(&self) -> WinfetchResult<()> {
+        for (name, value) in [("columns", self.Columns), ("cell_width", self.CellWidth), ("cell_height", self.CellHeight)] {
+            if value == 0 {
+                return Err(WinfetchError(format!("`{}` of the `image` table must be greater than zero", name)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct ProcessorUsageConfig {
+    // in milliseconds
+    #[serde(rename = "sample_interval")]
+    pub SampleInterval: u64
+}
+
+impl ProcessorUsageConfig {
+    pub fn SampleInterval(&self) -> Duration {
+        Duration::from_millis(self.SampleInterval)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct TopConfig {
+    #[serde(rename = "count")]
+    pub Count: usize,
+    #[serde(rename = "sort")]
+    pub Sort: ProcessSort
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    // all drive types are shown when empty
+    #[serde(rename = "drive_types")]
+    pub DriveTypes: Vec<DriveType>,
+    // in gibibytes, the units the sizes are printed in
+    #[serde(rename = "min_size")]
+    pub MinimumSize: f64,
+    #[serde(rename = "physical_disks")]
+    pub PhysicalDisks: bool
+}
+
+impl StorageConfig {
+    pub fn Filter(&self) -> StorageFilter {
+        StorageFilter {
+            DriveTypes: self.DriveTypes.clone(),
+            MinimumSize: (self.MinimumSize.max(0.0) * 1024.0f64.powi(3)) as u64
+        }
+    }
+}
+
+fn DefaultLanguage() -> String {
+    String::from("auto")
+}
+
+// toml table keys are always strings, so the module names are converted here
+fn DeserializeTemplates<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<Module, Template>, D::Error> {
+    BTreeMap::<String, Template>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, template)| match Module::FromName(&name) {
+            Some(module) => Ok((module, template)),
+            None => Err(D::Error::custom(format!("unknown module `{}` in templates", name)))
+        })
+        .collect()
+}
+
+impl Config {
+    pub fn GetConfigPath() -> PathBuf {
+        if let Ok(value) = env::var("WINFETCH_CONFIG") {
+            PathBuf::from(value)
+        }
+        else {
+            PathBuf::from(env::var("USERPROFILE").unwrap_or_default())
+                .join(".config")
+                .join("winfetch")
+                .join("config.toml")
+        }
+    }
+
+    pub fn LoadConfig() -> WinfetchResult<Self> {
+        let path = Self::GetConfigPath();
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Self::LoadConfigFrom(&path)
+    }
+
+    // unlike the default path, a path given explicitly has to exist
+    pub fn LoadConfigFrom(path: &Path) -> WinfetchResult<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|error| WinfetchError(format!("could not read config file `{}`; error: {}", path.display(), error)))?;
+
+        Self::ParseConfig(&content)
+            .map_err(|error| WinfetchError(format!("could not parse config file `{}`; error: {}", path.display(), error)))
+    }
+
+    pub fn ParseConfig(content: &str) -> WinfetchResult<Self> {
+        let config: Self = toml::from_str(content).map_err(|error| WinfetchError(error.to_string()))?;
+
+        if config.Language != "auto" && catalog::ResolveLanguage(&config.Language).is_none() {
+            return Err(WinfetchError(format!("unknown language `{}`; expected `auto` or one of {}", config.Language, catalog::LANGUAGES.join(", "))));
+        }
+
+        // the placeholders are checked here so that typos are reported at startup rather than rendered as empty strings
+        for (module, template) in &config.Templates {
+            template.Validate(module.TemplatePlaceholders())
+                .map_err(|error| WinfetchError(format!("invalid template for module `{}`; {}", module.Name(), error)))?;
+        }
+
+        config.Image.Validate()?;
+
+        for custom_module in &config.Custom {
+            custom_module.Validate()?;
+        }
+
+        Ok(config)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            Language: DefaultLanguage(),
+            Modules: Module::DefaultModules(),
+            Title: TitleConfig::default(),
+            ProcessorUsage: ProcessorUsageConfig::default(),
+            Top: TopConfig::default(),
+            Storage: StorageConfig::default(),
+            Layout: LayoutConfig::default(),
+            Image: ImageConfig::default(),
+            Templates: BTreeMap::new(),
+            Custom: Vec::new()
+        }
+    }
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            Path: None,
+            Protocol: None,
+            Columns: 32,
+            CellWidth: 10,
+            CellHeight: 20
+        }
+    }
+}
+
+impl Default for ProcessorUsageConfig {
+    fn default() -> Self {
+        Self {
+            SampleInterval: processorusage::DEFAULT_SAMPLE_INTERVAL.as_millis() as u64
+        }
+    }
+}
+
+impl Default for TopConfig {
+    fn default() -> Self {
+        Self {
+            Count: processes::DEFAULT_TOP_PROCESSES,
+            Sort: ProcessSort::Cpu
+        }
+    }
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            Overflow: Overflow::Wrap
+        }
+    }
+}
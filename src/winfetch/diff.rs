@@ -0,0 +1,202 @@
+use std::{
+    fs,
+    path::Path
+};
+
+use serde::Serialize;
+
+use serde_json::{
+    Map,
+    Value
+};
+
+use crate::winfetch::{
+    error::{
+        WinfetchError,
+        WinfetchResult
+    },
+    utils
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Change {
+    #[serde(rename = "path")]
+    pub Path: String,
+    #[serde(rename = "old")]
+    pub Old: Option<Value>,
+    #[serde(rename = "new")]
+    pub New: Option<Value>,
+    // for the free space of drives and memory, the change of the share of the capacity in use, in percentage points
+    #[serde(rename = "points", skip_serializing_if = "Option::is_none")]
+    pub PointsChange: Option<f64>
+}
+
+impl Change {
+    // the top level key of the report the change belongs to
+    pub fn Module(&self) -> &str {
+        self.Path.split(['.', '[']).next().unwrap_or_default()
+    }
+}
+
+// the modules whose values change on every run, which would drown the changes worth seeing
+const VOLATILE_KEYS: &[&str] = &["Uptime", "ProcessorUsage", "Processes", "Temperature", "Battery"];
+
+// the fields telling the elements of an array apart, so that reordered or inserted elements are matched with their
+// counterparts instead of the elements that happen to share their index; the first one present in every element is used
+const IDENTITY_KEYS: &[&str] = &["DriveLetter", "DeviceName", "DistributionName", "SessionId", "ProcessId", "Label", "Name"];
+
+// the free amounts and the capacities they are a share of
+const CAPACITY_KEYS: &[(&str, &str)] = &[("FreeSpace", "Size"), ("FreePhysicalMemory", "TotalVisibleMemorySize")];
+
+pub fn LoadReport(path: &Path) -> WinfetchResult<Value> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| WinfetchError(format!("could not read report `{}`; error: {}", path.display(), error)))?;
+
+    serde_json::from_str(&contents).map_err(|error| WinfetchError(format!("could not parse report `{}`; error: {}", path.display(), error)))
+}
+
+fn JoinPath(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    }
+    else {
+        format!("{}.{}", path, key)
+    }
+}
+
+// duplicate or missing identities cannot be matched, so arrays without a unique identity are compared by index
+fn IdentityKey(old: &[Value], new: &[Value]) -> Option<&'static str> {
+    let unique = |elements: &[Value], key: &str| elements
+        .iter()
+        .map(|element| element.get(key).filter(|identity| !identity.is_null()))
+        .collect::<Option<Vec<_>>>()
+        .is_some_and(|identities| identities.iter().enumerate().all(|(index, identity)| !identities[..index].contains(identity)));
+
+    IDENTITY_KEYS.iter().copied().find(|key| unique(old, key) && unique(new, key))
+}
+
+fn FindByIdentity<'a>(elements: &'a [Value], identity_key: &str, identity: Option<&Value>) -> Option<&'a Value> {
+    elements.iter().find(|element| element.get(identity_key) == identity)
+}
+
+// the share of the capacity in use, in percents
+fn UsedPercentage(object: &Map<String, Value>, free_key: &str, capacity_key: &str) -> Option<f64> {
+    let free = object.get(free_key)?.as_f64()?;
+    let capacity = object.get(capacity_key)?.as_f64()?;
+
+    (capacity > 0.0).then(|| (capacity - free) / capacity * 100.0)
+}
+
+fn DiffObjects(path: &str, old: &Map<String, Value>, new: &Map<String, Value>, changes: &mut Vec<Change>) {
+    for (key, old_value) in old {
+        let first_change = changes.len();
+        DiffValues(JoinPath(path, key), Some(old_value), new.get(key), changes);
+
+        if let (Some((free_key, capacity_key)), Some(change)) = (CAPACITY_KEYS.iter().find(|(free_key, _)| free_key == key), changes.get_mut(first_change)) {
+            change.PointsChange = UsedPercentage(new, free_key, capacity_key)
+                .zip(UsedPercentage(old, free_key, capacity_key))
+                .map(|(new, old)| new - old);
+        }
+    }
+
+    for (key, new_value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+        DiffValues(JoinPath(path, key), None, Some(new_value), changes);
+    }
+}
+
+fn DiffArrays(path: &str, old: &[Value], new: &[Value], changes: &mut Vec<Change>) {
+    let identity_key = match IdentityKey(old, new) {
+        Some(identity_key) => identity_key,
+        None => {
+            for index in 0..old.len().max(new.len()) {
+                DiffValues(format!("{}[{}]", path, index), old.get(index), new.get(index), changes);
+            }
+
+            return;
+        }
+    };
+
+    for old_element in old {
+        let identity = old_element.get(identity_key);
+        let path = format!("{}[{}]", path, identity.map(RenderValue).unwrap_or_default());
+
+        DiffValues(path, Some(old_element), FindByIdentity(new, identity_key, identity), changes);
+    }
+
+    for new_element in new.iter().filter(|new_element| FindByIdentity(old, identity_key, new_element.get(identity_key)).is_none()) {
+        let path = format!("{}[{}]", path, new_element.get(identity_key).map(RenderValue).unwrap_or_default());
+
+        DiffValues(path, None, Some(new_element), changes);
+    }
+}
+
+fn DiffValues(path: String, old: Option<&Value>, new: Option<&Value>, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => DiffObjects(&path, old, new, changes),
+        (Some(Value::Array(old)), Some(Value::Array(new))) => DiffArrays(&path, old, new, changes),
+        (old, new) if old != new => changes.push(Change {
+            Path: path,
+            Old: old.cloned(),
+            New: new.cloned(),
+            PointsChange: None
+        }),
+        _ => ()
+    }
+}
+
+// compares two reports saved with `--json`, walking into objects and arrays down to the values that differ; the volatile
+// modules are left out
+pub fn DiffReports(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let without_volatile = |report: &Value| match report {
+        Value::Object(report) => Value::Object(report
+            .iter()
+            .filter(|(key, _)| !VOLATILE_KEYS.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()),
+        report => report.clone()
+    };
+
+    DiffValues(String::new(), Some(&without_volatile(old)), Some(&without_volatile(new)), &mut changes);
+
+    changes
+}
+
+fn RenderValue(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string()
+    }
+}
+
+// every module with changes gets a heading, followed by the removed values in red and the added ones in green
+pub fn RenderDiff(changes: &[Change]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current_module = None;
+
+    for change in changes {
+        if current_module != Some(change.Module()) {
+            current_module = Some(change.Module());
+            lines.push(format!("{}34m{}{}0m", utils::ANSI_ESCAPE_SEQUENCE, change.Module(), utils::ANSI_ESCAPE_SEQUENCE));
+        }
+
+        if let Some(old) = &change.Old {
+            lines.push(format!("{}91m- {}: {}{}0m", utils::ANSI_ESCAPE_SEQUENCE, change.Path, RenderValue(old), utils::ANSI_ESCAPE_SEQUENCE));
+        }
+
+        if let Some(new) = &change.New {
+            let points_change = match change.PointsChange {
+                Some(points_change) => format!(" ({:+.1} points)", points_change),
+                None => String::new()
+            };
+
+            lines.push(format!("{}32m+ {}: {}{}{}0m", utils::ANSI_ESCAPE_SEQUENCE, change.Path, RenderValue(new), points_change, utils::ANSI_ESCAPE_SEQUENCE));
+        }
+    }
+
+    lines
+}
+
+pub fn RenderDiffJson(changes: &[Change]) -> String {
+    serde_json::to_string_pretty(changes).unwrap()
+}
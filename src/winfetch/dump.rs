@@ -0,0 +1,159 @@
+use std::{
+    fs,
+    mem,
+    path::Path,
+    sync::Mutex
+};
+
+use serde::{
+    de::DeserializeOwned,
+    Deserialize,
+    Serialize
+};
+
+use serde_json::Value;
+
+use crate::winfetch::error::{
+    WinfetchError,
+    WinfetchResult
+};
+
+// identifies a read from the system; a dump is replayed by looking the reads up by their key
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "source")]
+pub enum RecordKey {
+    #[serde(rename = "wmi")]
+    Wmi {
+        #[serde(rename = "namespace")]
+        Namespace: String,
+        #[serde(rename = "query")]
+        Query: String
+    },
+    #[serde(rename = "registry")]
+    Registry {
+        #[serde(rename = "operation")]
+        Operation: String,
+        #[serde(rename = "path")]
+        Path: String
+    },
+    // the win32 functions read outside of wmi and the registry
+    #[serde(rename = "call")]
+    Call {
+        #[serde(rename = "function")]
+        Function: String,
+        #[serde(rename = "argument", default, skip_serializing_if = "Option::is_none")]
+        Argument: Option<String>
+    }
+}
+
+impl RecordKey {
+    pub fn Call(function: &str) -> Self {
+        Self::Call { Function: function.to_string(), Argument: None }
+    }
+}
+
+// a read and what it returned; `value` is absent for reads that failed
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Record {
+    #[serde(flatten)]
+    pub Key: RecordKey,
+    #[serde(rename = "value", default, skip_serializing_if = "Option::is_none")]
+    pub Value: Option<Value>,
+    #[serde(rename = "error", default, skip_serializing_if = "Option::is_none")]
+    pub Error: Option<String>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RawDump {
+    #[serde(rename = "records")]
+    pub Records: Vec<Record>
+}
+
+impl RawDump {
+    pub fn LoadDump(path: &Path) -> WinfetchResult<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| WinfetchError(format!("could not read dump `{}`; error: {}", path.display(), error)))?;
+
+        serde_json::from_str(&contents).map_err(|error| WinfetchError(format!("could not parse dump `{}`; error: {}", path.display(), error)))
+    }
+
+    pub fn SaveDump(&self, path: &Path) -> WinfetchResult<()> {
+        fs::write(path, serde_json::to_string_pretty(self).unwrap())
+            .map_err(|error| WinfetchError(format!("could not write dump `{}`; error: {}", path.display(), error)))
+    }
+
+    // reads with the same key are returned in the order they were recorded, and the last one is repeated once they
+    // run out, so that watch mode keeps working
+    fn Replay<T: DeserializeOwned>(&self, key: &RecordKey, consumed: &mut [bool]) -> WinfetchResult<T> {
+        let index = self.Records
+            .iter()
+            .enumerate()
+            .position(|(index, record)| record.Key == *key && !consumed[index])
+            .or_else(|| self.Records.iter().rposition(|record| record.Key == *key))
+            .ok_or_else(|| WinfetchError(format!("the dump has no record of {}", serde_json::to_string(key).unwrap())))?;
+
+        consumed[index] = true;
+
+        let record = &self.Records[index];
+
+        match &record.Error {
+            Some(error) => Err(WinfetchError(error.clone())),
+            None => serde_json::from_value(record.Value.clone().unwrap_or(Value::Null))
+                .map_err(|error| WinfetchError(format!("invalid record of {}; error: {}", serde_json::to_string(key).unwrap(), error)))
+        }
+    }
+}
+
+enum DataSource {
+    Live,
+    Recording(RawDump),
+    Replaying(RawDump, Vec<bool>)
+}
+
+static DATA_SOURCE: Mutex<DataSource> = Mutex::new(DataSource::Live);
+
+// from now on, every read from the system is recorded until `FinishRecording` is called
+pub fn StartRecording() {
+    *DATA_SOURCE.lock().unwrap() = DataSource::Recording(RawDump::default());
+}
+
+pub fn FinishRecording() -> RawDump {
+    match mem::replace(&mut *DATA_SOURCE.lock().unwrap(), DataSource::Live) {
+        DataSource::Recording(dump) => dump,
+        _ => RawDump::default()
+    }
+}
+
+// from now on, the reads from the system are answered from `dump`, on any platform
+pub fn StartReplaying(dump: RawDump) {
+    let consumed = vec![false; dump.Records.len()];
+
+    *DATA_SOURCE.lock().unwrap() = DataSource::Replaying(dump, consumed);
+}
+
+pub fn IsReplaying() -> bool {
+    matches!(*DATA_SOURCE.lock().unwrap(), DataSource::Replaying(..))
+}
+
+// every read from the system goes through here, so that it can be recorded or replayed
+pub(crate) fn Intercept<T, F>(key: RecordKey, read: F) -> WinfetchResult<T>
+where
+    T: DeserializeOwned + Serialize,
+    F: FnOnce() -> WinfetchResult<T>
+{
+    if let DataSource::Replaying(dump, consumed) = &mut *DATA_SOURCE.lock().unwrap() {
+        return dump.Replay(&key, consumed);
+    }
+
+    // the lock is not held while reading, since reads can take a while
+    let result = read();
+
+    if let DataSource::Recording(dump) = &mut *DATA_SOURCE.lock().unwrap() {
+        dump.Records.push(match &result {
+            Ok(value) => Record { Key: key, Value: Some(serde_json::to_value(value).unwrap()), Error: None },
+            Err(WinfetchError(error)) => Record { Key: key, Value: None, Error: Some(error.clone()) }
+        });
+    }
+
+    result
+}
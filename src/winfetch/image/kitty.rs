@@ -0,0 +1,53 @@
+use crate::winfetch::image::Image;
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+pub fn EncodeBase64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let triple = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        encoded.push(BASE64_ALPHABET[(triple >> 18) as usize & 0x3F] as char);
+        encoded.push(BASE64_ALPHABET[(triple >> 12) as usize & 0x3F] as char);
+        encoded.push(if chunk.len() > 1 { BASE64_ALPHABET[(triple >> 6) as usize & 0x3F] as char } else { '=' });
+        encoded.push(if chunk.len() > 2 { BASE64_ALPHABET[triple as usize & 0x3F] as char } else { '=' });
+    }
+
+    encoded
+}
+
+pub fn EncodeKitty(image: &Image, columns: usize, rows: usize) -> String {
+    let rgba = image.Pixels.iter().flat_map(|pixel| pixel.iter().copied()).collect::<Vec<_>>();
+    let payload = EncodeBase64(&rgba);
+    let chunks = payload.as_bytes().chunks(KITTY_CHUNK_SIZE).collect::<Vec<_>>();
+
+    let mut sequence = String::new();
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = if index + 1 < chunks.len() { 1 } else { 0 };
+
+        // the control data is only sent with the first chunk; `C=1` keeps the cursor in place and `q=2` silences responses
+        if index == 0 {
+            sequence.push_str(&format!(
+                "\x1B_Gf=32,s={},v={},a=T,c={},r={},C=1,q=2,m={};",
+                image.Width,
+                image.Height,
+                columns,
+                rows,
+                more
+            ));
+        }
+        else {
+            sequence.push_str(&format!("\x1B_Gm={};", more));
+        }
+
+        sequence.push_str(std::str::from_utf8(chunk).unwrap());
+        sequence.push_str("\x1B\\");
+    }
+
+    sequence
+}
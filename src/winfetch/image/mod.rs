@@ -0,0 +1,150 @@
+use std::{
+    env,
+    fs,
+    path::Path
+};
+
+use serde::Deserialize;
+
+use crate::winfetch::error::{
+    WinfetchError,
+    WinfetchResult
+};
+
+pub mod kitty;
+pub mod sixel;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum ImageProtocol {
+    #[serde(rename = "kitty")]
+    Kitty,
+    #[serde(rename = "sixel")]
+    Sixel
+}
+
+impl ImageProtocol {
+    pub fn DetectImageProtocol() -> Option<Self> {
+        let term = env::var("TERM").unwrap_or_default();
+        let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+
+        if term == "xterm-kitty" || env::var("KITTY_WINDOW_ID").is_ok() || term_program == "WezTerm" {
+            Some(Self::Kitty)
+        }
+        else if env::var("WT_SESSION").is_ok() || term_program == "mintty" || term.contains("sixel") {
+            Some(Self::Sixel)
+        }
+        else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    pub Width: usize,
+    pub Height: usize,
+    pub Pixels: Vec<[u8; 4]>
+}
+
+impl Image {
+    pub fn LoadPNG(path: &Path) -> WinfetchResult<Self> {
+        let bytes = fs::read(path)
+            .map_err(|error| WinfetchError(format!("could not read image `{}`; error: {}", path.display(), error)))?;
+
+        Self::FromPNG(&bytes)
+            .map_err(|error| WinfetchError(format!("could not decode image `{}`; error: {}", path.display(), error)))
+    }
+
+    pub fn FromPNG(bytes: &[u8]) -> WinfetchResult<Self> {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+        let (info, mut reader) = decoder.read_info().map_err(|error| WinfetchError(error.to_string()))?;
+        let mut buffer = vec![0u8; info.buffer_size()];
+        reader.next_frame(&mut buffer).map_err(|error| WinfetchError(error.to_string()))?;
+
+        let pixels = match info.color_type {
+            png::ColorType::RGBA => buffer.chunks(4).map(|chunk| [chunk[0], chunk[1], chunk[2], chunk[3]]).collect(),
+            png::ColorType::RGB => buffer.chunks(3).map(|chunk| [chunk[0], chunk[1], chunk[2], 255]).collect(),
+            png::ColorType::GrayscaleAlpha => buffer.chunks(2).map(|chunk| [chunk[0], chunk[0], chunk[0], chunk[1]]).collect(),
+            png::ColorType::Grayscale => buffer.iter().map(|&value| [value, value, value, 255]).collect(),
+            color_type => return Err(WinfetchError(format!("unsupported color type: {:?}", color_type)))
+        };
+
+        Ok(Self {
+            Width: info.width as usize,
+            Height: info.height as usize,
+            Pixels: pixels
+        })
+    }
+
+    pub fn Pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        self.Pixels[y * self.Width + x]
+    }
+
+    // box-filter resize to the given width, keeping the aspect ratio
+    pub fn ResizeToWidth(&self, width: usize) -> Self {
+        let width = width.max(1);
+        let height = ((self.Height * width) as f64 / self.Width as f64).round().max(1.0) as usize;
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            let source_y_start = y * self.Height / height;
+            let source_y_end = ((y + 1) * self.Height / height).max(source_y_start + 1);
+
+            for x in 0..width {
+                let source_x_start = x * self.Width / width;
+                let source_x_end = ((x + 1) * self.Width / width).max(source_x_start + 1);
+
+                let mut sum = [0usize; 4];
+                let mut count = 0;
+
+                for source_y in source_y_start..source_y_end {
+                    for source_x in source_x_start..source_x_end {
+                        let pixel = self.Pixel(source_x, source_y);
+
+                        sum.iter_mut().zip(pixel.iter()).for_each(|(total, &channel)| *total += channel as usize);
+                        count += 1;
+                    }
+                }
+
+                pixels.push([
+                    (sum[0] / count) as u8,
+                    (sum[1] / count) as u8,
+                    (sum[2] / count) as u8,
+                    (sum[3] / count) as u8
+                ]);
+            }
+        }
+
+        Self {
+            Width: width,
+            Height: height,
+            Pixels: pixels
+        }
+    }
+}
+
+pub struct EncodedImage {
+    pub Sequence: String,
+    pub Columns: usize,
+    pub Rows: usize
+}
+
+impl EncodedImage {
+    pub fn EncodeImage(image: &Image, protocol: ImageProtocol, columns: usize, cell_width: usize, cell_height: usize) -> Self {
+        let resized = image.ResizeToWidth(columns * cell_width);
+        let rows = resized.Height.div_ceil(cell_height);
+
+        let sequence = match protocol {
+            ImageProtocol::Kitty => kitty::EncodeKitty(&resized, columns, rows),
+            ImageProtocol::Sixel => sixel::EncodeSixel(&resized)
+        };
+
+        Self {
+            Sequence: sequence,
+            Columns: columns,
+            Rows: rows
+        }
+    }
+}
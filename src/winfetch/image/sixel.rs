@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+
+use crate::winfetch::image::Image;
+
+// pixels with an alpha below this are left transparent
+const SIXEL_ALPHA_THRESHOLD: u8 = 128;
+
+// quantizes a color onto the 6x6x6 color cube, returning the palette register
+pub fn QuantizeColor(pixel: [u8; 4]) -> usize {
+    let level = |channel: u8| (channel as usize * 5 + 127) / 255;
+
+    level(pixel[0]) * 36 + level(pixel[1]) * 6 + level(pixel[2])
+}
+
+fn PaletteDefinition(register: usize) -> String {
+    let percentage = |level: usize| level * 100 / 5;
+
+    format!("#{};2;{};{};{}", register, percentage(register / 36), percentage(register / 6 % 6), percentage(register % 6))
+}
+
+fn PushRun(sequence: &mut String, sixel: char, count: usize) {
+    if count > 3 {
+        sequence.push_str(&format!("!{}{}", count, sixel));
+    }
+    else {
+        (0..count).for_each(|_| sequence.push(sixel));
+    }
+}
+
+pub fn EncodeSixel(image: &Image) -> String {
+    let registers = image.Pixels.iter()
+        .map(|&pixel| if pixel[3] >= SIXEL_ALPHA_THRESHOLD { Some(QuantizeColor(pixel)) } else { None })
+        .collect::<Vec<_>>();
+
+    // `P2=1` leaves unset pixels transparent; the raster attributes set a 1:1 pixel aspect ratio
+    let mut sequence = format!("\x1BP0;1;0q\"1;1;{};{}", image.Width, image.Height);
+
+    registers.iter().flatten().collect::<BTreeSet<_>>().into_iter().for_each(|&register| {
+        sequence.push_str(&PaletteDefinition(register))
+    });
+
+    for band in (0..image.Height).step_by(6) {
+        let band_height = (image.Height - band).min(6);
+        let band_registers = (band..band + band_height)
+            .flat_map(|y| registers[y * image.Width..(y + 1) * image.Width].iter().flatten())
+            .collect::<BTreeSet<_>>();
+
+        for (index, &&register) in band_registers.iter().enumerate() {
+            if index > 0 {
+                sequence.push('$');
+            }
+
+            sequence.push_str(&format!("#{}", register));
+
+            let sixels = (0..image.Width)
+                .map(|x| {
+                    let bits = (0..band_height)
+                        .filter(|&row| registers[(band + row) * image.Width + x] == Some(register))
+                        .fold(0u8, |bits, row| bits | 1 << row);
+
+                    (b'?' + bits) as char
+                })
+                .collect::<Vec<_>>();
+            let trimmed_length = sixels.iter().rposition(|&sixel| sixel != '?').map_or(0, |position| position + 1);
+
+            let mut run_sixel = '?';
+            let mut run_length = 0;
+
+            for &sixel in &sixels[..trimmed_length] {
+                if sixel == run_sixel {
+                    run_length += 1;
+                    continue;
+                }
+
+                PushRun(&mut sequence, run_sixel, run_length);
+                run_sixel = sixel;
+                run_length = 1;
+            }
+
+            PushRun(&mut sequence, run_sixel, run_length);
+        }
+
+        sequence.push('-');
+    }
+
+    sequence.push_str("\x1B\\");
+    sequence
+}
@@ -0,0 +1,230 @@
+use std::env;
+
+#[cfg(windows)]
+use std::mem;
+
+use serde::Deserialize;
+
+#[cfg(windows)]
+use winapi::um::{
+    processenv::GetStdHandle as Win32_GetStdHandle,
+    winbase::STD_OUTPUT_HANDLE,
+    wincon::{
+        GetConsoleScreenBufferInfo as Win32_GetConsoleScreenBufferInfo,
+        CONSOLE_SCREEN_BUFFER_INFO
+    }
+};
+
+use crate::winfetch::{
+    image::EncodedImage,
+    module::InfoLine,
+    report::SystemReport,
+    utils
+};
+
+pub fn RenderInfoLine(line: &InfoLine) -> String {
+    match line.Label() {
+        Some(label) => format!("{}34m{}{}0m: {}", utils::ANSI_ESCAPE_SEQUENCE, label, utils::ANSI_ESCAPE_SEQUENCE, line.Value()),
+        None => line.Value().to_string()
+    }
+}
+
+pub fn RenderPlainInfoLine(line: &InfoLine) -> String {
+    match line.Label() {
+        Some(label) => format!("{}: {}", label, utils::StripANSIEscapeSequences(line.Value())),
+        None => utils::StripANSIEscapeSequences(line.Value())
+    }
+}
+
+// what happens to the lines wider than the terminal
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum Overflow {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "truncate")]
+    Truncate,
+    #[serde(rename = "wrap")]
+    Wrap,
+    // multi-valued modules are printed one value per line, and the remaining long lines are truncated
+    #[serde(rename = "split")]
+    Split
+}
+
+// the width of the console window, or of `COLUMNS` when the output is not a console
+pub fn GetTerminalWidth() -> Option<usize> {
+    #[cfg(windows)]
+    {
+        let mut info: CONSOLE_SCREEN_BUFFER_INFO = unsafe { mem::zeroed() };
+
+        if unsafe { Win32_GetConsoleScreenBufferInfo(Win32_GetStdHandle(STD_OUTPUT_HANDLE), &mut info) } != 0 {
+            return Some((info.srWindow.Right - info.srWindow.Left + 1) as usize);
+        }
+    }
+
+    env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok())
+}
+
+pub fn LogoWidth(logo: &str) -> usize {
+    logo.lines().map(utils::VisibleWidth).max().unwrap_or(0)
+}
+
+// fits the lines into `width` columns; continuation lines are aligned under the value of the line they continue
+pub fn FitInfoLines(lines: Vec<InfoLine>, width: usize, overflow: Overflow) -> Vec<InfoLine> {
+    let mut fitted = Vec::new();
+
+    for line in lines {
+        let label_width = line.Label().map_or(0, |label| utils::VisibleWidth(&label) + 2);
+
+        if overflow == Overflow::None || label_width + utils::VisibleWidth(line.Value()) <= width {
+            fitted.push(line);
+            continue;
+        }
+
+        let value_width = width.saturating_sub(label_width);
+
+        match overflow {
+            Overflow::Wrap if label_width > 0 && value_width > 0 => {
+                let mut parts = utils::WrapVisible(line.Value(), value_width).into_iter();
+                let first = parts.next().unwrap_or_default();
+
+                fitted.push(line.WithValue(first));
+                fitted.extend(parts.map(|part| InfoLine::Unlabeled(format!("{}{}", " ".repeat(label_width), part))));
+            },
+            _ => {
+                let value = utils::TruncateVisible(line.Value(), value_width);
+                fitted.push(line.WithValue(value));
+            }
+        }
+    }
+
+    fitted
+}
+
+pub fn RenderWithLogo(logo: &str, lines: &[InfoLine]) -> Vec<String> {
+    let logo_lines = logo.lines().collect::<Vec<_>>();
+    let logo_width = LogoWidth(logo);
+
+    (0..logo_lines.len().max(lines.len()))
+        .map(|index| {
+            let logo_line = logo_lines.get(index).copied().unwrap_or("");
+            let padding = logo_width - utils::VisibleWidth(logo_line);
+
+            match lines.get(index) {
+                Some(line) => format!("{}{}0m{}  {}", logo_line, utils::ANSI_ESCAPE_SEQUENCE, " ".repeat(padding), RenderInfoLine(line)),
+                None => format!("{}{}0m", logo_line, utils::ANSI_ESCAPE_SEQUENCE)
+            }
+        })
+        .collect()
+}
+
+pub fn RenderWithoutLogo(lines: &[InfoLine]) -> Vec<String> {
+    lines.iter().map(RenderInfoLine).collect()
+}
+
+pub fn RenderWithImage(image: &EncodedImage, lines: &[InfoLine]) -> Vec<String> {
+    let offset = format!("{}{}C", utils::ANSI_ESCAPE_SEQUENCE, image.Columns + 2);
+
+    // reserve the rows first so that a scroll does not invalidate the saved cursor position, then draw
+    // the image and put the cursor back at its top-left corner for the info column
+    let mut output = vec![format!(
+        "{}{}{}A\x1B7{}\x1B8",
+        "\n".repeat(image.Rows),
+        utils::ANSI_ESCAPE_SEQUENCE,
+        image.Rows,
+        image.Sequence
+    )];
+
+    if let Some(line) = lines.first() {
+        output[0].push_str(&format!("{}{}", offset, RenderInfoLine(line)));
+    }
+
+    lines.iter().skip(1).for_each(|line| output.push(format!("{}{}", offset, RenderInfoLine(line))));
+
+    while output.len() < image.Rows {
+        output.push(String::new());
+    }
+
+    output
+}
+
+pub fn RenderStdout(lines: &[InfoLine]) -> Vec<String> {
+    lines.iter().map(RenderPlainInfoLine).collect()
+}
+
+pub fn RenderJson(report: &SystemReport) -> String {
+    serde_json::to_string_pretty(report).unwrap()
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html
+}
+
+impl ReportFormat {
+    pub fn FromName(name: &str) -> Option<Self> {
+        match name {
+            "markdown" | "md" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            _ => None
+        }
+    }
+}
+
+// the title becomes the heading; the labeled lines become a table
+pub fn RenderMarkdown(lines: &[InfoLine]) -> String {
+    let mut markdown = String::new();
+
+    if let Some(InfoLine::Unlabeled(title)) = lines.first() {
+        markdown.push_str(&format!("# {}\n\n", utils::StripANSIEscapeSequences(title)));
+    }
+
+    markdown.push_str("| Module | Value |\n");
+    markdown.push_str("| ------ | ----- |\n");
+
+    for line in lines {
+        if let Some(label) = line.Label() {
+            markdown.push_str(&format!(
+                "| {} | {} |\n",
+                label.replace('|', "\\|"),
+                utils::StripANSIEscapeSequences(line.Value()).replace('|', "\\|")
+            ));
+        }
+    }
+
+    markdown
+}
+
+pub fn RenderHtml(logo: &str, lines: &[InfoLine]) -> String {
+    let title = match lines.first() {
+        Some(InfoLine::Unlabeled(title)) => utils::StripANSIEscapeSequences(title),
+        _ => String::from("winfetch")
+    };
+    let body = RenderWithLogo(logo, lines)
+        .iter()
+        .map(|line| utils::ANSIToHTML(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<style>
+body {{ background: #1e1e1e; color: #cccccc; }}
+pre {{ font-family: Consolas, \"Cascadia Mono\", monospace; }}
+</style>
+</head>
+<body>
+<pre>
+{}
+</pre>
+</body>
+</html>
+",
+        utils::EscapeHTML(&title),
+        body
+    )
+}
@@ -1,4 +1,50 @@
-mod __internals;
-pub mod error;
-pub mod model;
-pub mod utils;
\ No newline at end of file
+mod __internals;
+pub mod catalog;
+pub mod config;
+pub mod diff;
+pub mod dump;
+pub mod error;
+pub mod image;
+pub mod layout;
+pub mod model;
+pub mod module;
+pub mod report;
+pub mod template;
+pub mod utils;
+pub mod watch;
+
+pub const WINFETCH_HELP_MESSAGE: &str = r"Usage: winfetch [OPTION]...
+  or:  winfetch diff OLD NEW [--json]
+Prints information about the system next to a logo.
+
+Options:
+    --help              prints this message and exit
+    --version           displays version information and exit
+    --config <path>     reads the configuration from <path> instead of the default location
+    --only <modules>    prints only the comma-separated <modules>, in the given order
+    --hide <modules>    leaves out the comma-separated <modules>
+    --logo <name>       prints the `windows` or the `small` logo
+    --no-logo           prints the information without a logo
+    --image <path>      shows the PNG image at <path> as the logo, if the terminal supports it
+    --stdout            prints only `Label: value` lines, without the logo and without escape codes
+    --json              prints the collected system report as JSON
+    --format <format>   prints a `markdown` or `html` report
+    --watch [seconds]   redraws the output every [seconds] (1 by default) until Ctrl+C is pressed
+    --dump <path>       records the raw system data read for the report to <path>
+    --from-dump <path>  renders the report from the raw system data recorded in <path>, on any platform
+
+The `diff` command compares two reports saved with `--json`.
+
+Source code for this command: <https://github.com/LinuxCommandsForWindows/LinuxCommandsOnWindows/tree/main/src/winfetch>";
+
+pub const WINFETCH_VERSION_MESSAGE: &str = r"winfetch (Linux Commands for Windows) 0.1.2
+Copyright (c) 2021 Linux Commands for Windows Project Developers
+License Apache v2.0: <https://www.apache.org/licenses/LICENSE-2.0.txt>
+This is free software: you are free to modify and redistribute it.
+There is NO WARRANTY, to the extent permitted by law.";
+
+pub use report::{
+    Collect,
+    Options,
+    SystemReport
+};
\ No newline at end of file
@@ -0,0 +1,135 @@
+use std::{
+    fmt,
+    time::Duration
+};
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::wmi::{
+        self,
+        WmiRow
+    },
+    catalog,
+    error::WinfetchResult,
+    utils
+};
+
+// `EstimatedRunTime` is this many minutes while the battery is not discharging
+const UNKNOWN_RUN_TIME: i64 = 71582788;
+
+// the charges below which a battery is shown in yellow and red
+const LOW_THRESHOLD: u32 = 50;
+const CRITICAL_THRESHOLD: u32 = 20;
+
+// the values of `Win32_Battery.BatteryStatus`, folded into what matters to the user
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum BatteryState {
+    #[serde(rename = "discharging")]
+    Discharging,
+    #[serde(rename = "charging")]
+    Charging,
+    #[serde(rename = "full")]
+    Full,
+    // on ac power, but not charging
+    #[serde(rename = "plugged_in")]
+    PluggedIn,
+    #[serde(rename = "unknown")]
+    Unknown
+}
+
+impl BatteryState {
+    pub fn FromBatteryStatus(battery_status: i64) -> Self {
+        match battery_status {
+            // 4 and 5 are discharging with a low and a critical charge
+            1 | 4 | 5 => Self::Discharging,
+            2 | 11 => Self::PluggedIn,
+            3 => Self::Full,
+            6..=9 => Self::Charging,
+            _ => Self::Unknown
+        }
+    }
+}
+
+impl fmt::Display for BatteryState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", catalog::Message(match self {
+            Self::Discharging => "discharging",
+            Self::Charging => "charging",
+            Self::Full => "fully_charged",
+            Self::PluggedIn => "plugged_in",
+            Self::Unknown => "unknown"
+        }))
+    }
+}
+
+#[derive(Serialize)]
+pub struct BatteryInfo {
+    pub Name: String,
+    /// The remaining charge, from 0 to 100.
+    pub ChargePercentage: u32,
+    pub State: BatteryState,
+    /// The estimated time until the battery is empty; `None` unless it is discharging.
+    pub RemainingTime: Option<Duration>
+}
+
+impl BatteryInfo {
+    // batteries without a charge are left out
+    pub fn FromBattery(row: &WmiRow) -> Option<Self> {
+        let state = BatteryState::FromBatteryStatus(row.get("BatteryStatus").and_then(|value| value.AsInteger()).unwrap_or_default());
+        let remaining_time = row
+            .get("EstimatedRunTime")
+            .and_then(|value| value.AsInteger())
+            .filter(|&minutes| state == BatteryState::Discharging && minutes != UNKNOWN_RUN_TIME)
+            .map(|minutes| Duration::from_secs(minutes as u64 * 60));
+
+        Some(Self {
+            Name: row.get("Name").and_then(|value| value.AsString()).unwrap_or_default().trim().to_string(),
+            ChargePercentage: row.get("EstimatedChargeRemaining")?.AsInteger()?.clamp(0, 100) as u32,
+            State: state,
+            RemainingTime: remaining_time
+        })
+    }
+}
+
+impl fmt::Display for BatteryInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let color = if self.ChargePercentage < CRITICAL_THRESHOLD {
+            "91m"
+        }
+        else if self.ChargePercentage < LOW_THRESHOLD {
+            "93m"
+        }
+        else {
+            "32m"
+        };
+
+        write!(f, "{}{}{}%{}0m ({}", utils::ANSI_ESCAPE_SEQUENCE, color, self.ChargePercentage, utils::ANSI_ESCAPE_SEQUENCE, self.State)?;
+
+        if let Some(remaining_time) = self.RemainingTime {
+            let minutes = remaining_time.as_secs() / 60;
+
+            write!(f, ", {}", catalog::Format("remaining", &[("time", &format!("{}:{:02}", minutes / 60, minutes % 60))]))?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+#[derive(Serialize)]
+pub struct Battery {
+    /// Empty on machines without a battery.
+    pub Batteries: Vec<BatteryInfo>
+}
+
+impl Battery {
+    pub fn GetBattery() -> WinfetchResult<Self> {
+        let rows = wmi::__InternalsWmiQuery(
+            wmi::ROOT_CIMV2,
+            "SELECT Name, BatteryStatus, EstimatedChargeRemaining, EstimatedRunTime FROM Win32_Battery",
+            &["Name", "BatteryStatus", "EstimatedChargeRemaining", "EstimatedRunTime"]
+        )?;
+
+        Ok(Self { Batteries: rows.iter().filter_map(BatteryInfo::FromBattery).collect() })
+    }
+}
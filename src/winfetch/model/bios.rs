@@ -0,0 +1,112 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::{
+        self,
+        registry::{
+            self,
+            RegistryHive
+        },
+        wmi
+    },
+    catalog,
+    error::{
+        WinfetchError,
+        WinfetchResult
+    }
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum BootMode {
+    Legacy,
+    UEFI
+}
+
+impl fmt::Display for BootMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "{}", catalog::Message("legacy_bios")),
+            Self::UEFI => write!(f, "UEFI")
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Bios {
+    /// Whether the firmware booted windows as uefi or as a legacy bios.
+    pub BootMode: BootMode,
+    pub Manufacturer: String,
+    /// The release date of the firmware, as a calendar date without a time zone.
+    pub ReleaseDate: Option<NaiveDate>,
+    /// Whether secure boot is enabled; `None` on legacy bios systems, which do not have it.
+    pub SecureBoot: Option<bool>,
+    /// The firmware version, as reported in the smbios tables.
+    pub SMBIOSBIOSVersion: String
+}
+
+impl Bios {
+    pub fn GetBios() -> WinfetchResult<Self> {
+        let rows = wmi::__InternalsWmiQuery(
+            wmi::ROOT_CIMV2,
+            "SELECT Manufacturer, SMBIOSBIOSVersion, ReleaseDate FROM Win32_BIOS",
+            &["Manufacturer", "SMBIOSBIOSVersion", "ReleaseDate"]
+        )?;
+        let row = rows.first().ok_or_else(|| WinfetchError(String::from("`Win32_BIOS` returned no rows")))?;
+
+        let manufacturer = wmi::__InternalsWmiGet(row, "Manufacturer")?.AsString().unwrap_or_default();
+        let smbios_bios_version = wmi::__InternalsWmiGet(row, "SMBIOSBIOSVersion")?.AsString().unwrap_or_default();
+        // some firmwares leave the date out or fill it with garbage, which only hides the date
+        let release_date = wmi::__InternalsWmiGet(row, "ReleaseDate")?
+            .AsString()
+            .and_then(|release_date| __internals::__InternalsParseCIMDateTime(release_date).ok())
+            .map(|release_date| release_date.naive_local().date());
+
+        // `PEFirmwareType` is 1 for legacy bios and 2 for uefi
+        let boot_mode = match registry::__InternalsRegistryGetDword(RegistryHive::LocalMachine, r"SYSTEM\CurrentControlSet\Control", "PEFirmwareType")? {
+            2 => BootMode::UEFI,
+            _ => BootMode::Legacy
+        };
+
+        // the secure boot state only exists on uefi systems
+        let secure_boot = match boot_mode {
+            BootMode::UEFI => registry::__InternalsRegistryGetDword(
+                RegistryHive::LocalMachine,
+                r"SYSTEM\CurrentControlSet\Control\SecureBoot\State",
+                "UEFISecureBootEnabled"
+            )
+                .ok()
+                .map(|enabled| enabled != 0),
+            BootMode::Legacy => None
+        };
+
+        Ok(Self {
+            BootMode: boot_mode,
+            Manufacturer: manufacturer.to_string(),
+            ReleaseDate: release_date,
+            SecureBoot: secure_boot,
+            SMBIOSBIOSVersion: smbios_bios_version.to_string()
+        })
+    }
+}
+
+impl fmt::Display for Bios {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.Manufacturer, self.SMBIOSBIOSVersion)?;
+
+        if let Some(release_date) = self.ReleaseDate {
+            write!(f, " ({})", release_date.format("%Y-%m-%d"))?;
+        }
+
+        write!(f, ", {}", self.BootMode)?;
+
+        match self.SecureBoot {
+            Some(true) => write!(f, ", {}", catalog::Message("secure_boot_on")),
+            Some(false) => write!(f, ", {}", catalog::Message("secure_boot_off")),
+            None => Ok(())
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    env,
+    fmt,
+    fs,
+    io::Read,
+    path::PathBuf,
+    process::{
+        Command,
+        Stdio
+    },
+    thread,
+    time::{
+        Duration,
+        Instant
+    }
+};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+use regex::Regex;
+
+use serde::{
+    de::Error,
+    Deserialize,
+    Deserializer,
+    Serialize
+};
+
+use crate::winfetch::{
+    dump::{
+        self,
+        RecordKey
+    },
+    error::{
+        WinfetchError,
+        WinfetchResult
+    },
+    utils
+};
+
+const DEFAULT_TIMEOUT: u64 = 2000;
+
+// how often a running command is checked for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+pub enum Shell {
+    #[default]
+    #[serde(rename = "cmd")]
+    Cmd,
+    #[serde(rename = "powershell")]
+    PowerShell
+}
+
+impl Shell {
+    fn Command(&self, command: &str) -> Command {
+        match self {
+            // `cmd` does its own parsing of the command line, so the command is passed without quoting
+            Self::Cmd => {
+                let mut shell = Command::new("cmd");
+                shell.arg("/C");
+
+                #[cfg(windows)]
+                shell.raw_arg(command);
+                #[cfg(not(windows))]
+                shell.arg(command);
+
+                shell
+            },
+            Self::PowerShell => {
+                let mut shell = Command::new("powershell");
+                shell.args(["-NoProfile", "-NonInteractive", "-Command", command]);
+
+                shell
+            }
+        }
+    }
+}
+
+// a line defined in the `custom` array of the config; its value comes from exactly one of `command`, `env` or `file`
+#[derive(Clone, Debug, Deserialize)]
+pub struct CustomModule {
+    #[serde(rename = "label")]
+    pub Label: String,
+    #[serde(rename = "command")]
+    pub Command: Option<String>,
+    #[serde(rename = "shell", default)]
+    pub Shell: Shell,
+    #[serde(rename = "env")]
+    pub Environment: Option<String>,
+    #[serde(rename = "file")]
+    pub File: Option<PathBuf>,
+    /// How long the command may run, in milliseconds.
+    #[serde(rename = "timeout", default = "DefaultTimeout")]
+    pub Timeout: u64,
+    #[serde(rename = "regex", default, deserialize_with = "DeserializeRegex")]
+    pub Regex: Option<Regex>
+}
+
+fn DefaultTimeout() -> u64 {
+    DEFAULT_TIMEOUT
+}
+
+// the expression is compiled when the config is loaded, so that mistakes are reported at startup
+fn DeserializeRegex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Regex>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|pattern| Regex::new(&pattern).map_err(|error| D::Error::custom(format!("invalid regex `{}`; {}", pattern, error))))
+        .transpose()
+}
+
+impl CustomModule {
+    pub fn Validate(&self) -> WinfetchResult<()> {
+        let sources = [self.Command.is_some(), self.Environment.is_some(), self.File.is_some()];
+
+        if sources.iter().filter(|&&source| source).count() != 1 {
+            return Err(WinfetchError(format!("custom module `{}` needs exactly one of `command`, `env` or `file`", self.Label)));
+        }
+
+        Ok(())
+    }
+
+    fn RunCommand(&self, command: &str) -> WinfetchResult<String> {
+        let mut process = self.Shell
+            .Command(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|error| WinfetchError(format!("could not run `{}`; error: {}", command, error)))?;
+
+        // the output is read on another thread so that a command filling the pipe does not block until the timeout
+        let mut stdout = process.stdout.take().unwrap();
+        let reader = thread::spawn(move || {
+            let mut output = Vec::new();
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+
+        let start = Instant::now();
+        let timeout = Duration::from_millis(self.Timeout);
+
+        let status = loop {
+            match process.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if start.elapsed() >= timeout => {
+                    process.kill().ok();
+                    process.wait().ok();
+
+                    return Err(WinfetchError(format!("`{}` timed out after {} ms", command, self.Timeout)));
+                },
+                Ok(None) => thread::sleep(POLL_INTERVAL),
+                Err(error) => return Err(WinfetchError(format!("could not wait for `{}`; error: {}", command, error)))
+            }
+        };
+
+        if !status.success() {
+            return Err(WinfetchError(format!("`{}` failed with {}", command, status)));
+        }
+
+        let output = reader
+            .join()
+            .unwrap()
+            .map_err(|error| WinfetchError(format!("could not read the output of `{}`; error: {}", command, error)))?;
+
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    // the output is recorded in dumps under the label of the line
+    fn ReadSource(&self) -> WinfetchResult<String> {
+        let key = RecordKey::Call { Function: String::from("ReadCustomSource"), Argument: Some(self.Label.clone()) };
+
+        dump::Intercept(key, || self.ReadSourceLive())
+    }
+
+    fn ReadSourceLive(&self) -> WinfetchResult<String> {
+        if let Some(command) = &self.Command {
+            self.RunCommand(command)
+        }
+        else if let Some(variable) = &self.Environment {
+            env::var(variable).map_err(|_| WinfetchError(format!("environment variable `{}` is not set", variable)))
+        }
+        else if let Some(path) = &self.File {
+            fs::read_to_string(path).map_err(|error| WinfetchError(format!("could not read `{}`; error: {}", path.display(), error)))
+        }
+        else {
+            Err(WinfetchError(format!("custom module `{}` has no source", self.Label)))
+        }
+    }
+
+    pub fn GetCustomValue(&self) -> CustomValue {
+        let (value, error) = match self.ReadSource().and_then(|output| ExtractValue(&output, self.Regex.as_ref())) {
+            Ok(value) => (Some(value), None),
+            Err(WinfetchError(error)) => (None, Some(error))
+        };
+
+        CustomValue {
+            Label: self.Label.clone(),
+            Value: value,
+            Error: error
+        }
+    }
+}
+
+// without a regex the first non-empty line is used; with one, the first capture group or else the whole match
+pub fn ExtractValue(output: &str, regex: Option<&Regex>) -> WinfetchResult<String> {
+    match regex {
+        Some(regex) => regex
+            .captures(output)
+            .and_then(|captures| captures.get(1).or_else(|| captures.get(0)))
+            .map(|value| value.as_str().trim().to_string())
+            .ok_or_else(|| WinfetchError(format!("no match for `{}`", regex.as_str()))),
+        None => Ok(output.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or_default().to_string())
+    }
+}
+
+#[derive(Serialize)]
+pub struct CustomValue {
+    pub Label: String,
+    pub Value: Option<String>,
+    pub Error: Option<String>
+}
+
+impl fmt::Display for CustomValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.Value, &self.Error) {
+            (Some(value), _) => write!(f, "{}", value),
+            (None, Some(error)) => write!(f, "{}91m{}{}0m", utils::ANSI_ESCAPE_SEQUENCE, error, utils::ANSI_ESCAPE_SEQUENCE),
+            (None, None) => Ok(())
+        }
+    }
+}
@@ -1,292 +1,35 @@
-use std::{
-    ffi::{
-        OsStr,
-        OsString,
-    },
-    fmt,
-    mem,
-    os::windows::ffi::{
-        OsStrExt,
-        OsStringExt
-    },
-    ptr,
-    slice
-};
-
-use winapi::{
-    shared::{
-        ntdef::NULL,
-        rpcdce::{
-            RPC_C_AUTHN_LEVEL_CALL,
-            RPC_C_AUTHN_WINNT,
-            RPC_C_AUTHZ_NONE,
-            RPC_C_IMP_LEVEL_IMPERSONATE
-        },
-        winerror::S_OK,
-        wtypes::VT_BSTR,
-        wtypesbase::CLSCTX_INPROC_SERVER
-    },
-    um::{
-        combaseapi::{
-            CoCreateInstance as Win32_CoCreateInstance,
-            CoSetProxyBlanket as Win32_CoSetProxyBlanket,
-            CoUninitialize as Win32_CoUninitialize
-        },
-        oaidl::VARIANT,
-        objbase::CoInitialize as Win32_CoInitialize,
-        objidl::EOAC_NONE,
-        oleauto::VariantClear as Win32_VariantClear,
-        wbemcli::{
-            CLSID_WbemLocator,
-            IEnumWbemClassObject,
-            IID_IWbemLocator,
-            IWbemClassObject,
-            IWbemLocator,
-            IWbemServices,
-            WBEM_FLAG_FORWARD_ONLY,
-            WBEM_FLAG_RETURN_IMMEDIATELY,
-            WBEM_INFINITE
-        }
-    }
-};
-
-use crate::winfetch::error::{
-    WinfetchError,
-    WinfetchResult
-};
-
-pub struct HostSystem {
-    Manufacturer: OsString,
-    Model: OsString
-}
-
-impl HostSystem {
-    pub fn GetHostSystemInformation() -> WinfetchResult<Self> {
-        let mut wbem_locator_c_void = NULL;
-
-        unsafe {
-            match Win32_CoInitialize(
-                NULL
-            ) {
-                S_OK => (),
-                error_code => return Err(WinfetchError(format!("failed to initialize com library; error code: {}", error_code)))
-            }
-
-            match Win32_CoCreateInstance(
-                &CLSID_WbemLocator,
-                ptr::null_mut(),
-                CLSCTX_INPROC_SERVER,
-                &IID_IWbemLocator,
-                &mut wbem_locator_c_void
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to create wbem locator; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_locator = ptr::NonNull::new(wbem_locator_c_void as *mut IWbemLocator).unwrap();
-        let mut root_cimv2 = AsRef::<OsStr>::as_ref(r"ROOT\CIMV2")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut wbem_service_null = ptr::null_mut::<IWbemServices>();
-
-        unsafe {
-            match (*wbem_locator.as_ptr()).ConnectServer(
-                root_cimv2.as_mut_ptr(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                0,
-                ptr::null_mut(),
-                ptr::null_mut(),
-                &mut wbem_service_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to connect to wbem server; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_service_nonnull = ptr::NonNull::new(wbem_service_null).unwrap();
-
-        unsafe {
-            match Win32_CoSetProxyBlanket(
-                wbem_service_nonnull.as_ptr() as _,
-                RPC_C_AUTHN_WINNT,
-                RPC_C_AUTHZ_NONE,
-                ptr::null_mut(),
-                RPC_C_AUTHN_LEVEL_CALL,
-                RPC_C_IMP_LEVEL_IMPERSONATE,
-                NULL,
-                EOAC_NONE
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to set proxy; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut wql = AsRef::<OsStr>::as_ref(r"WQL")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let mut host_system_query = AsRef::<OsStr>::as_ref(r"SELECT * FROM Win32_ComputerSystem")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut enum_wbem_class_object_null = NULL as *mut IEnumWbemClassObject;
-
-        unsafe {
-            match (*wbem_service_nonnull.as_ptr()).ExecQuery(
-                wql.as_mut_ptr(),
-                host_system_query.as_mut_ptr(),
-                (WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY) as i32,
-                ptr::null_mut(),
-                &mut enum_wbem_class_object_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to execute query; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut wbem_class_object_null = NULL as *mut IWbemClassObject;
-        let mut return_value = 0;
-
-        unsafe {
-            match (*enum_wbem_class_object_null).Next(
-                WBEM_INFINITE as i32,
-                1,
-                &mut wbem_class_object_null,
-                &mut return_value
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("could not get next element of enumeration; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let manufacturer_property_name = AsRef::<OsStr>::as_ref(r"Manufacturer")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let model_property_name = AsRef::<OsStr>::as_ref(r"Model")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let mut manufacturer_property = unsafe {
-            mem::zeroed::<VARIANT>()
-        };
-        let mut model_property = unsafe {
-            mem::zeroed::<VARIANT>()
-        };
-
-        unsafe {
-            match (*wbem_class_object_null).Get(
-                manufacturer_property_name.as_ptr(),
-                0,
-                &mut manufacturer_property,
-                ptr::null_mut(),
-                ptr::null_mut()
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("could not get `Manufacturer` property of wbem class object; error code: {}", error_code)))
-                }
-            }
-
-            match (*wbem_class_object_null).Get(
-                model_property_name.as_ptr(),
-                0,
-                &mut model_property,
-                ptr::null_mut(),
-                ptr::null_mut()
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("could not get `Model` property of wbem class object; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let manufacturer_variant_type = unsafe {
-            manufacturer_property.n1.n2().vt
-        };
-        let manufacturer_variant_value = unsafe {
-            match manufacturer_variant_type as u32 {
-                VT_BSTR => {
-                    let bstr_ptr = manufacturer_property.n1.n2().n3.bstrVal();
-                    let mut i = 0;
-
-                    while *bstr_ptr.offset(i) != 0 {
-                        i += 1;
-                    }
-
-                    let slice = slice::from_raw_parts(*bstr_ptr, i as usize + 1);
-                    OsString::from(OsString::from_wide(slice).into_string().unwrap().replace('\0', ""))
-                },
-                _ => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))
-                }
-            }
-        };
-
-        let model_variant_type = unsafe {
-            model_property.n1.n2().vt
-        };
-        let model_variant_value = unsafe {
-            match model_variant_type as u32 {
-                VT_BSTR => {
-                    let bstr_ptr = model_property.n1.n2().n3.bstrVal();
-                    let mut i = 0;
-
-                    while *bstr_ptr.offset(i) != 0 {
-                        i += 1;
-                    }
-
-                    let slice = slice::from_raw_parts(*bstr_ptr, i as usize + 1);
-                    OsString::from(OsString::from_wide(slice).into_string().unwrap().replace('\0', ""))
-                },
-                _ => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))
-                }
-            }
-        };
-
-        unsafe {
-            Win32_VariantClear(&mut manufacturer_property);
-            Win32_VariantClear(&mut model_property);
-
-            Win32_CoUninitialize();
-        }
-
-        Ok(Self {
-            Manufacturer: manufacturer_variant_value,
-            Model: model_variant_value
-        })
-    }
-}
-
-impl fmt::Display for HostSystem {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.Manufacturer.to_str().unwrap(), self.Model.to_str().unwrap())
-    }
-}
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::wmi,
+    error::{
+        WinfetchError,
+        WinfetchResult
+    }
+};
+
+#[derive(Serialize)]
+pub struct HostSystem {
+    pub Manufacturer: String,
+    pub Model: String
+}
+
+impl HostSystem {
+    pub fn GetHostSystemInformation() -> WinfetchResult<Self> {
+        let rows = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT Manufacturer, Model FROM Win32_ComputerSystem", &["Manufacturer", "Model"])?;
+        let row = rows.first().ok_or_else(|| WinfetchError(String::from("`Win32_ComputerSystem` returned no rows")))?;
+
+        Ok(Self {
+            Manufacturer: wmi::__InternalsWmiGet(row, "Manufacturer")?.AsString().unwrap_or_default().to_string(),
+            Model: wmi::__InternalsWmiGet(row, "Model")?.AsString().unwrap_or_default().to_string()
+        })
+    }
+}
+
+impl fmt::Display for HostSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.Manufacturer, self.Model)
+    }
+}
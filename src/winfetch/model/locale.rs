@@ -0,0 +1,167 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::registry::{
+        self,
+        RegistryHive,
+        RegistryKey
+    },
+    catalog,
+    error::WinfetchResult
+};
+
+const INTERNATIONAL_KEY: &str = r"Control Panel\International";
+const MUI_CACHED_KEY: &str = r"Control Panel\Desktop\MuiCached";
+const TIME_ZONE_KEY: &str = r"SYSTEM\CurrentControlSet\Control\TimeZoneInformation";
+const KEYBOARD_LAYOUT_KEY: &str = "Keyboard Layout";
+const KEYBOARD_LAYOUTS_KEY: &str = r"SYSTEM\CurrentControlSet\Control\Keyboard Layouts";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TimeZone {
+    /// The standard name of the time zone, like `W. Europe Standard Time`.
+    pub Name: String,
+    /// The minutes to add to the local time to get utc, including the daylight saving bias while it is in effect.
+    pub ActiveBias: i32,
+    pub DaylightSaving: bool
+}
+
+impl TimeZone {
+    // the biases are `REG_DWORD` values holding signed numbers
+    pub fn FromRegistryKey(key: &RegistryKey) -> Option<Self> {
+        let bias = |name: &str| key.Values.get(name).and_then(|value| value.AsDword()).map(|bias| bias as i32);
+
+        let name = key.Values.get("TimeZoneKeyName").and_then(|value| value.AsString())?;
+        let standard_bias = bias("Bias")?;
+        let active_bias = bias("ActiveTimeBias").unwrap_or(standard_bias);
+
+        Some(Self {
+            Name: name.trim_end_matches('\0').to_string(),
+            ActiveBias: active_bias,
+            DaylightSaving: active_bias != standard_bias
+        })
+    }
+
+    pub fn UtcOffset(&self) -> String {
+        FormatUtcOffset(-self.ActiveBias)
+    }
+}
+
+// `UTC+05:30` for an offset of 330 minutes
+pub fn FormatUtcOffset(minutes: i32) -> String {
+    format!("UTC{}{:02}:{:02}", if minutes < 0 { '-' } else { '+' }, minutes.abs() / 60, minutes.abs() % 60)
+}
+
+// the layout identifiers of the `Preload` subkey, in order, with the substitutes applied; the values are named `1`, `2`
+// and so on
+pub fn PreloadedLayouts(keyboard_layout_key: &RegistryKey) -> Vec<String> {
+    let mut preload = keyboard_layout_key.SubKeys
+        .get("Preload")
+        .map(|preload| preload.Values
+            .iter()
+            .filter_map(|(index, value)| Some((index.parse::<u32>().ok()?, value.AsString()?.to_string())))
+            .collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    preload.sort();
+
+    preload
+        .into_iter()
+        .map(|(_, layout)| keyboard_layout_key.SubKeys
+            .get("Substitutes")
+            .and_then(|substitutes| substitutes.Values.get(&layout))
+            .and_then(|substitute| substitute.AsString())
+            .map(String::from)
+            .unwrap_or(layout))
+        .collect()
+}
+
+// the display language is the first of the user's languages; accounts that never set any follow the machine
+fn DisplayLanguage(international: &RegistryKey) -> Option<String> {
+    international.SubKeys
+        .get("User Profile")
+        .and_then(|user_profile| user_profile.Values.get("Languages"))
+        .and_then(|languages| languages.AsMultiString())
+        .and_then(|languages| languages.first().cloned())
+        .or_else(|| registry::__InternalsRegistryReadKey(RegistryHive::CurrentUser, MUI_CACHED_KEY)
+            .ok()
+            .flatten()
+            .and_then(|mui_cached| mui_cached.Values.get("MachinePreferredUILanguages")?.AsMultiString()?.first().cloned()))
+}
+
+// `en-US`, without the rest of the locale
+pub fn GetDisplayLanguage() -> Option<String> {
+    DisplayLanguage(&registry::__InternalsRegistryReadKey(RegistryHive::CurrentUser, INTERNATIONAL_KEY).ok()??)
+}
+
+#[derive(Serialize)]
+pub struct Locale {
+    /// The language tag of the user interface, like `en-US`.
+    pub DisplayLanguage: Option<String>,
+    /// The locale of the dates, times and numbers, like `en-GB`.
+    pub RegionalFormat: Option<String>,
+    /// The short date pattern, like `dd/MM/yyyy`.
+    pub ShortDateFormat: Option<String>,
+    pub TimeZone: Option<TimeZone>,
+    /// The keyboard layouts configured for the user, which are not necessarily the ones loaded in the current session.
+    pub KeyboardLayouts: Vec<String>
+}
+
+impl Locale {
+    pub fn GetLocale() -> WinfetchResult<Self> {
+        let international = registry::__InternalsRegistryReadKey(RegistryHive::CurrentUser, INTERNATIONAL_KEY)?.unwrap_or_default();
+        let value = |key: &RegistryKey, name: &str| key.Values.get(name).and_then(|value| value.AsString()).map(String::from);
+
+        let time_zone = registry::__InternalsRegistryReadKey(RegistryHive::LocalMachine, TIME_ZONE_KEY)?
+            .as_ref()
+            .and_then(TimeZone::FromRegistryKey);
+
+        let keyboard_layouts = registry::__InternalsRegistryReadKey(RegistryHive::CurrentUser, KEYBOARD_LAYOUT_KEY)?
+            .map(|keyboard_layout_key| PreloadedLayouts(&keyboard_layout_key))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|layout| registry::__InternalsRegistryGetString(RegistryHive::LocalMachine, &format!(r"{}\{}", KEYBOARD_LAYOUTS_KEY, layout), "Layout Text")
+                .unwrap_or(layout))
+            .collect();
+
+        Ok(Self {
+            DisplayLanguage: DisplayLanguage(&international),
+            RegionalFormat: value(&international, "LocaleName"),
+            ShortDateFormat: value(&international, "sShortDate"),
+            TimeZone: time_zone,
+            KeyboardLayouts: keyboard_layouts
+        })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+
+        if let Some(display_language) = &self.DisplayLanguage {
+            parts.push(display_language.clone());
+        }
+
+        if let Some(regional_format) = &self.RegionalFormat {
+            let regional_format = catalog::Format("regional_format", &[("locale", regional_format)]);
+
+            parts.push(match &self.ShortDateFormat {
+                Some(short_date_format) => format!("{} ({})", regional_format, short_date_format),
+                None => regional_format
+            });
+        }
+
+        if let Some(time_zone) = &self.TimeZone {
+            let daylight_saving = if time_zone.DaylightSaving { format!(", {}", catalog::Message("dst")) } else { String::new() };
+
+            parts.push(format!("{} ({}{})", time_zone.Name, time_zone.UtcOffset(), daylight_saving));
+        }
+
+        if !self.KeyboardLayouts.is_empty() {
+            parts.push(catalog::Format("configured_layouts", &[("layouts", &self.KeyboardLayouts.join(", "))]));
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
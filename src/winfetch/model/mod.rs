@@ -1,12 +1,21 @@
-pub mod graphicscard;
-pub mod hostsystem;
-pub mod memory;
-pub mod motherboard;
-pub mod names;
-pub mod os;
-pub mod processor;
-pub mod processorusage;
-pub mod screenres;
-pub mod storage;
-pub mod uptime;
-pub mod winntkernel;
\ No newline at end of file
+pub mod battery;
+pub mod bios;
+pub mod custom;
+pub mod graphicscard;
+pub mod hostsystem;
+pub mod locale;
+pub mod memory;
+pub mod motherboard;
+pub mod names;
+pub mod os;
+pub mod processes;
+pub mod processor;
+pub mod processorusage;
+pub mod screenres;
+pub mod storage;
+pub mod temperature;
+pub mod uptime;
+pub mod users;
+pub mod virtualization;
+pub mod winntkernel;
+pub mod wsl;
\ No newline at end of file
@@ -1,55 +1,195 @@
-use std::{
-    ffi::OsString,
-    fmt,
-    os::windows::ffi::OsStringExt,
-    slice
-};
-
-use winapi::um::winbase::{
-    GetComputerNameW as Win32_GetComputerNameW,
-    GetUserNameW as Win32_GetUserNameW,
-};
-
-use crate::winfetch::utils;
-
-pub struct Names {
-    pub ComputerName: OsString,
-    pub UserName: OsString
-}
-
-impl Names {
-    pub fn GetNames() -> Self {
-        let mut computer_name_buffer = vec![0u16; 32767];
-        let computer_name = unsafe {
-            Win32_GetComputerNameW(computer_name_buffer.as_mut_ptr(), &mut 32767);
-
-            let slice = slice::from_raw_parts(computer_name_buffer.as_ptr(), computer_name_buffer.len());
-            OsString::from(OsString::from_wide(slice).into_string().unwrap().replace('\0', ""))
-        };
-
-        let mut user_name_buffer = vec![0u16; 32767];
-        let user_name = unsafe {
-            Win32_GetUserNameW(user_name_buffer.as_mut_ptr(), &mut 32767);
-
-            let slice = slice::from_raw_parts(user_name_buffer.as_ptr(), user_name_buffer.len());
-            OsString::from(OsString::from_wide(slice).into_string().unwrap().replace('\0', ""))
-        };
-
-        Self {
-            ComputerName: computer_name,
-            UserName: user_name
-        }
-    }
-}
-
-impl fmt::Display for Names {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}34m{}{}0m@{}34m{}{}0m",
-               utils::ANSI_ESCAPE_SEQUENCE,
-               self.UserName.clone().into_string().unwrap().to_ascii_lowercase(),
-               utils::ANSI_ESCAPE_SEQUENCE,
-               utils::ANSI_ESCAPE_SEQUENCE,
-               self.ComputerName.clone().into_string().unwrap().to_ascii_lowercase(),
-               utils::ANSI_ESCAPE_SEQUENCE)
-    }
-}
+use std::fmt;
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::{
+        minwindef::{
+            DWORD,
+            PULONG
+        },
+        ntdef::{
+            BOOLEAN,
+            LPWSTR
+        }
+    },
+    um::{
+        sysinfoapi::{
+            ComputerNameDnsFullyQualified,
+            GetComputerNameExW as Win32_GetComputerNameExW
+        },
+        winbase::{
+            GetComputerNameW as Win32_GetComputerNameW,
+            GetUserNameW as Win32_GetUserNameW,
+        }
+    }
+};
+
+use crate::winfetch::{
+    __internals::wmi,
+    dump::{
+        self,
+        RecordKey
+    },
+    error::WinfetchResult,
+    utils
+};
+
+// the values of `EXTENDED_NAME_FORMAT`
+#[cfg(windows)]
+const NAME_SAM_COMPATIBLE: DWORD = 2;
+#[cfg(windows)]
+const NAME_USER_PRINCIPAL: DWORD = 8;
+
+// `secext.h` is not covered by winapi
+#[cfg(windows)]
+#[link(name = "secur32")]
+extern "system" {
+    fn GetUserNameExW(NameFormat: DWORD, lpNameBuffer: LPWSTR, nSize: PULONG) -> BOOLEAN;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum Membership {
+    Domain(String),
+    Workgroup(String)
+}
+
+impl Membership {
+    // `Win32_ComputerSystem` sets `Domain` to the workgroup name as well when the computer is not part of a domain
+    pub fn FromComputerSystem(part_of_domain: bool, domain: Option<&str>, workgroup: Option<&str>) -> Option<Self> {
+        match (part_of_domain, domain, workgroup) {
+            (true, Some(domain), _) => Some(Self::Domain(domain.to_string())),
+            (false, _, Some(workgroup)) | (false, Some(workgroup), None) => Some(Self::Workgroup(workgroup.to_string())),
+            _ => None
+        }
+    }
+}
+
+// how the names are printed in the title, with or without a template
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+pub enum NameCase {
+    #[default]
+    #[serde(rename = "lower")]
+    Lower,
+    #[serde(rename = "preserve")]
+    Preserve
+}
+
+impl NameCase {
+    pub fn Apply(&self, name: &str) -> String {
+        match self {
+            Self::Lower => name.to_ascii_lowercase(),
+            Self::Preserve => name.to_string()
+        }
+    }
+}
+
+impl fmt::Display for Membership {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Domain(domain) => write!(f, "domain {}", domain),
+            Self::Workgroup(workgroup) => write!(f, "workgroup {}", workgroup)
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Names {
+    pub ComputerName: String,
+    pub UserName: String,
+    /// The account name qualified with its domain, like `DOMAIN\user`.
+    pub SamCompatibleName: Option<String>,
+    pub UserPrincipalName: Option<String>,
+    pub FullyQualifiedName: Option<String>,
+    pub Membership: Option<Membership>
+}
+
+// the names read with win32 calls, recorded together in dumps
+#[derive(Default, Deserialize, Serialize)]
+struct AccountNames {
+    ComputerName: Option<String>,
+    UserName: Option<String>,
+    SamCompatibleName: Option<String>,
+    UserPrincipalName: Option<String>,
+    FullyQualifiedName: Option<String>
+}
+
+// calls a win32 function filling a wide string buffer, returning `None` when it fails
+#[cfg(windows)]
+fn GetWideString<F: Fn(LPWSTR, &mut u32) -> bool>(function: F) -> Option<String> {
+    let mut buffer = vec![0u16; 32767];
+    let mut length = buffer.len() as u32;
+
+    if !function(buffer.as_mut_ptr(), &mut length) {
+        return None;
+    }
+
+    let end = buffer.iter().position(|&char| char == 0).unwrap_or(buffer.len());
+
+    Some(String::from_utf16_lossy(&buffer[..end])).filter(|string| !string.is_empty())
+}
+
+#[cfg(not(windows))]
+fn GetAccountNamesLive() -> WinfetchResult<AccountNames> {
+    Err(crate::winfetch::__internals::__InternalsUnsupportedPlatform("GetUserNameW"))
+}
+
+#[cfg(windows)]
+fn GetAccountNamesLive() -> WinfetchResult<AccountNames> {
+    Ok(AccountNames {
+        ComputerName: GetWideString(|buffer, length| unsafe { Win32_GetComputerNameW(buffer, length) } != 0),
+        UserName: GetWideString(|buffer, length| unsafe { Win32_GetUserNameW(buffer, length) } != 0),
+        // local accounts have no user principal name, so the calls below are allowed to fail
+        SamCompatibleName: GetWideString(|buffer, length| unsafe { GetUserNameExW(NAME_SAM_COMPATIBLE, buffer, length) } != 0),
+        UserPrincipalName: GetWideString(|buffer, length| unsafe { GetUserNameExW(NAME_USER_PRINCIPAL, buffer, length) } != 0),
+        FullyQualifiedName: GetWideString(|buffer, length| unsafe {
+            Win32_GetComputerNameExW(ComputerNameDnsFullyQualified, buffer, length)
+        } != 0)
+    })
+}
+
+impl Names {
+    pub fn GetNames() -> Self {
+        let names = dump::Intercept(RecordKey::Call("GetAccountNames"), GetAccountNamesLive).unwrap_or_default();
+
+        let membership = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT PartOfDomain, Domain, Workgroup FROM Win32_ComputerSystem", &["PartOfDomain", "Domain", "Workgroup"])
+            .ok()
+            .and_then(|rows| rows.into_iter().next())
+            .and_then(|row| Membership::FromComputerSystem(
+                row.get("PartOfDomain").and_then(|value| value.AsBoolean()).unwrap_or_default(),
+                row.get("Domain").and_then(|value| value.AsString()),
+                row.get("Workgroup").and_then(|value| value.AsString())
+            ));
+
+        Self {
+            ComputerName: names.ComputerName.unwrap_or_default(),
+            UserName: names.UserName.unwrap_or_default(),
+            SamCompatibleName: names.SamCompatibleName,
+            UserPrincipalName: names.UserPrincipalName,
+            FullyQualifiedName: names.FullyQualifiedName,
+            Membership: membership
+        }
+    }
+}
+
+impl Names {
+    pub fn Title(&self, case: NameCase) -> String {
+        format!("{}34m{}{}0m@{}34m{}{}0m",
+                utils::ANSI_ESCAPE_SEQUENCE,
+                case.Apply(&self.UserName),
+                utils::ANSI_ESCAPE_SEQUENCE,
+                utils::ANSI_ESCAPE_SEQUENCE,
+                case.Apply(&self.ComputerName),
+                utils::ANSI_ESCAPE_SEQUENCE)
+    }
+}
+
+impl fmt::Display for Names {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.Title(NameCase::default()))
+    }
+}
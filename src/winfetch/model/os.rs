@@ -1,404 +1,189 @@
-use std::{
-    ffi::{
-        OsStr,
-        OsString,
-    },
-    fmt,
-    mem,
-    os::windows::ffi::{
-        OsStrExt,
-        OsStringExt
-    },
-    ptr,
-    slice
-};
-
-use winapi::{
-    shared::{
-        minwindef::{
-            DWORD,
-            HKEY,
-        },
-        ntdef::NULL,
-        rpcdce::{
-            RPC_C_AUTHN_LEVEL_CALL,
-            RPC_C_AUTHN_WINNT,
-            RPC_C_AUTHZ_NONE,
-            RPC_C_IMP_LEVEL_IMPERSONATE
-        },
-        winerror::{
-            ERROR_SUCCESS,
-            S_OK
-        },
-        wtypes::VT_BSTR,
-        wtypesbase::CLSCTX_INPROC_SERVER
-    },
-    um::{
-        combaseapi::{
-            CoCreateInstance as Win32_CoCreateInstance,
-            CoSetProxyBlanket as Win32_CoSetProxyBlanket,
-            CoUninitialize as Win32_CoUninitialize
-        },
-        oaidl::VARIANT,
-        objbase::CoInitialize as Win32_CoInitialize,
-        objidl::EOAC_NONE,
-        oleauto::VariantClear as Win32_VariantClear,
-        wbemcli::{
-            CLSID_WbemLocator,
-            IEnumWbemClassObject,
-            IID_IWbemLocator,
-            IWbemClassObject,
-            IWbemLocator,
-            IWbemServices,
-            WBEM_FLAG_FORWARD_ONLY,
-            WBEM_FLAG_RETURN_IMMEDIATELY,
-            WBEM_INFINITE
-        },
-        winnt::{
-            KEY_READ,
-            PVOID
-        },
-        winreg::{
-            HKEY_LOCAL_MACHINE,
-            RegCloseKey as Win32_RegCloseKey,
-            RegGetValueW as Win32_RegGetValueW,
-            RegOpenKeyExW as Win32_RegOpenKeyExW,
-            RRF_RT_REG_SZ
-        },
-    }
-};
-
-use crate::winfetch::error::{
-    WinfetchError,
-    WinfetchResult
-};
-
-pub struct OS {
-    DisplayVersion: OsString,
-    OSArchitecture: OsString,
-    ProductName: OsString
-}
-
-impl OS {
-    pub fn GetOperatingSystemVersion() -> WinfetchResult<Self> {
-        let mut current_version_hkey: HKEY = ptr::null_mut();
-        let current_version_key = AsRef::<OsStr>::as_ref(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let current_version_handle = unsafe {
-            match Win32_RegOpenKeyExW(
-                HKEY_LOCAL_MACHINE,
-                current_version_key.as_ptr(),
-                0,
-                KEY_READ,
-                &mut current_version_hkey
-            ) as DWORD {
-                ERROR_SUCCESS => current_version_hkey,
-                error_code => {
-                    Win32_RegCloseKey(HKEY_LOCAL_MACHINE);  // close the key as we are aborting from the retrieving process
-                    return Err(WinfetchError(
-                        format!("could not get registry key from `HKEY_LOCAL_MACHINE`. (exit code: {})", error_code)
-                    ));
-                }
-            }
-        };
-
-        let product_name_key = AsRef::<OsStr>::as_ref("ProductName")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut buffer_len_product_name: DWORD = 0;
-
-        unsafe {
-            match Win32_RegGetValueW(
-                current_version_handle,
-                ptr::null_mut(),
-                product_name_key.as_ptr(),
-                RRF_RT_REG_SZ,
-                ptr::null_mut(),
-                ptr::null_mut(),
-                &mut buffer_len_product_name
-            ) as DWORD {
-                ERROR_SUCCESS => (),
-                error_code => {
-                    Win32_RegCloseKey(current_version_handle);  // close the key as we are aborting from the retrieving process
-                    Win32_RegCloseKey(HKEY_LOCAL_MACHINE);  // close the key as we are aborting from the retrieving process
-                    return Err(WinfetchError(
-                        format!("could not get buffer length from `HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\ProductName`; error code: {}", error_code)
-                    ));
-                }
-            }
-        }
-
-        let mut buffer_product_name = vec![0u16; buffer_len_product_name as usize];
-
-        let product_name = unsafe {
-            match Win32_RegGetValueW(
-                current_version_handle,
-                ptr::null_mut(),
-                product_name_key.as_ptr(),
-                RRF_RT_REG_SZ,
-                ptr::null_mut(),
-                buffer_product_name.as_mut_ptr() as PVOID,
-                &mut buffer_len_product_name
-            ) as DWORD {
-                ERROR_SUCCESS => buffer_product_name,
-                error_code => {
-                    Win32_RegCloseKey(current_version_handle);  // close the key as we are aborting from the retrieving process
-                    Win32_RegCloseKey(HKEY_LOCAL_MACHINE);  // close the key as we are aborting from the retrieving process
-                    return Err(WinfetchError(
-                        format!("could not get registry key from `HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\ProductName`; error code: {}", error_code)
-                    ));
-                }
-            }
-        };
-
-        let display_version_key = AsRef::<OsStr>::as_ref("DisplayVersion")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut buffer_len_display_version: DWORD = 0;
-
-        unsafe {
-            match Win32_RegGetValueW(
-                current_version_handle,
-                ptr::null_mut(),
-                display_version_key.as_ptr(),
-                RRF_RT_REG_SZ,
-                ptr::null_mut(),
-                ptr::null_mut(),
-                &mut buffer_len_display_version
-            ) as DWORD {
-                ERROR_SUCCESS => (),
-                error_code => {
-                    Win32_RegCloseKey(current_version_handle);  // close the key as we are aborting from the retrieving process
-                    Win32_RegCloseKey(HKEY_LOCAL_MACHINE);  // close the key as we are aborting from the retrieving process
-                    return Err(WinfetchError(
-                        format!("could not get buffer length from `HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\DisplayVersion`; error code: {}", error_code)
-                    ));
-                }
-            }
-        }
-
-        let mut buffer_display_version = vec![0u16; buffer_len_display_version as usize];
-
-        let display_version = unsafe {
-            match Win32_RegGetValueW(
-                current_version_handle,
-                ptr::null_mut(),
-                display_version_key.as_ptr(),
-                RRF_RT_REG_SZ,
-                ptr::null_mut(),
-                buffer_display_version.as_mut_ptr() as PVOID,
-                &mut buffer_len_display_version
-            ) as DWORD {
-                ERROR_SUCCESS => buffer_display_version,
-                error_code => {
-                    Win32_RegCloseKey(current_version_handle);  // close the key as we are aborting from the retrieving process
-                    Win32_RegCloseKey(HKEY_LOCAL_MACHINE);  // close the key as we are aborting from the retrieving process
-                    return Err(WinfetchError(
-                        format!("could not get registry value from `HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\DisplayVersion`; error code: {}", error_code)
-                    ));
-                }
-            }
-        };
-
-        unsafe {
-            Win32_RegCloseKey(current_version_handle);  // close the key as we are done with it
-            Win32_RegCloseKey(HKEY_LOCAL_MACHINE);  // close the key as we are done with it
-        }
-
-        Ok(Self {
-            DisplayVersion: OsString::from(OsString::from_wide(display_version.as_slice()).into_string().unwrap().replace('\0', "")),
-            OSArchitecture: OsString::new(),
-            ProductName: OsString::from(OsString::from_wide(product_name.as_slice()).into_string().unwrap().replace('\0', ""))
-        })
-    }
-
-    pub fn GetOperatingSystemArchitecture(&mut self) -> WinfetchResult<()> {
-        let mut wbem_locator_c_void = NULL;
-
-        unsafe {
-            match Win32_CoInitialize(
-                NULL
-            ) {
-                S_OK => (),
-                error_code => return Err(WinfetchError(format!("failed to initialize com library; error code: {}", error_code)))
-            }
-
-            match Win32_CoCreateInstance(
-                &CLSID_WbemLocator,
-                ptr::null_mut(),
-                CLSCTX_INPROC_SERVER,
-                &IID_IWbemLocator,
-                &mut wbem_locator_c_void
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to create wbem locator; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_locator = ptr::NonNull::new(wbem_locator_c_void as *mut IWbemLocator).unwrap();
-        let mut root_cimv2 = AsRef::<OsStr>::as_ref(r"ROOT\CIMV2")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut wbem_service_null = ptr::null_mut::<IWbemServices>();
-
-        unsafe {
-            match (*wbem_locator.as_ptr()).ConnectServer(
-                root_cimv2.as_mut_ptr(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                0,
-                ptr::null_mut(),
-                ptr::null_mut(),
-                &mut wbem_service_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to connect to wbem server; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_service_nonnull = ptr::NonNull::new(wbem_service_null).unwrap();
-
-        unsafe {
-            match Win32_CoSetProxyBlanket(
-                wbem_service_nonnull.as_ptr() as _,
-                RPC_C_AUTHN_WINNT,
-                RPC_C_AUTHZ_NONE,
-                ptr::null_mut(),
-                RPC_C_AUTHN_LEVEL_CALL,
-                RPC_C_IMP_LEVEL_IMPERSONATE,
-                NULL,
-                EOAC_NONE
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to set proxy; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut wql = AsRef::<OsStr>::as_ref(r"WQL")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let mut operating_system_query = AsRef::<OsStr>::as_ref("SELECT * FROM Win32_OperatingSystem")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut enum_wbem_class_object_null = NULL as *mut IEnumWbemClassObject;
-
-        unsafe {
-            match (*wbem_service_nonnull.as_ptr()).ExecQuery(
-                wql.as_mut_ptr(),
-                operating_system_query.as_mut_ptr(),
-                (WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY) as i32,
-                ptr::null_mut(),
-                &mut enum_wbem_class_object_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to execute query; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut wbem_class_object_null = NULL as *mut IWbemClassObject;
-        let mut return_value = 0;
-
-        unsafe {
-            match (*enum_wbem_class_object_null).Next(
-                WBEM_INFINITE as i32,
-                1,
-                &mut wbem_class_object_null,
-                &mut return_value
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("could not get next element of enumeration; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let os_architecture_property_name = AsRef::<OsStr>::as_ref(r"OSArchitecture")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let mut os_architecture_property = unsafe {
-            mem::zeroed::<VARIANT>()
-        };
-
-        unsafe {
-            match (*wbem_class_object_null).Get(
-                os_architecture_property_name.as_ptr(),
-                0,
-                &mut os_architecture_property,
-                ptr::null_mut(),
-                ptr::null_mut()
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("could not get `OSArchitecture` property of wbem class object; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let variant_type = unsafe {
-            os_architecture_property.n1.n2().vt
-        };
-        let variant_value = unsafe {
-            match variant_type as u32 {
-                VT_BSTR => {
-                    let bstr_ptr = os_architecture_property.n1.n2().n3.bstrVal();
-                    let mut i = 0;
-
-                    while *bstr_ptr.offset(i) != 0 {
-                        i += 1;
-                    }
-
-                    let slice = slice::from_raw_parts(*bstr_ptr, i as usize + 1);
-                    OsString::from(OsString::from_wide(slice).into_string().unwrap().replace('\0', ""))
-                },
-                _ => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))
-                }
-            }
-        };
-
-        unsafe {
-            Win32_VariantClear(&mut os_architecture_property);
-
-            Win32_CoUninitialize();
-        };
-
-        self.OSArchitecture.push(variant_value);
-
-        Ok(())
-    }
-}
-
-impl fmt::Display for OS {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}, Version {} [{}]", self.ProductName.to_str().unwrap(), self.DisplayVersion.to_str().unwrap(), self.OSArchitecture.to_str().unwrap())
-    }
-}
+use std::fmt;
+
+use chrono::{
+    DateTime,
+    Local,
+    TimeZone
+};
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::{
+        self,
+        registry::{
+            self,
+            RegistryHive
+        },
+        wmi::{
+            self,
+            WmiValue
+        }
+    },
+    catalog,
+    error::{
+        WinfetchError,
+        WinfetchResult
+    }
+};
+
+const CURRENT_VERSION_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion";
+
+// the application id of windows itself in the software licensing service
+const WINDOWS_APPLICATION_ID: &str = "55c92734-d682-4d71-983e-d6ec3f16059f";
+
+// the first build of windows 11; its registry `ProductName` still reads "Windows 10"
+const WINDOWS_11_FIRST_BUILD: u32 = 22000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum ActivationStatus {
+    Unlicensed,
+    Licensed,
+    OutOfBoxGrace,
+    OutOfToleranceGrace,
+    NonGenuineGrace,
+    Notification,
+    ExtendedGrace
+}
+
+impl ActivationStatus {
+    pub fn FromLicenseStatus(license_status: i64) -> WinfetchResult<Self> {
+        Ok(match license_status {
+            0 => Self::Unlicensed,
+            1 => Self::Licensed,
+            2 => Self::OutOfBoxGrace,
+            3 => Self::OutOfToleranceGrace,
+            4 => Self::NonGenuineGrace,
+            5 => Self::Notification,
+            6 => Self::ExtendedGrace,
+            _ => return Err(WinfetchError(format!("invalid license status, expected value within 0 and 6 (inclusive); actual value: {}", license_status)))
+        })
+    }
+}
+
+impl fmt::Display for ActivationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", catalog::Message(match self {
+            Self::Unlicensed => "not_activated",
+            Self::Licensed => "activated",
+            Self::OutOfBoxGrace => "initial_grace",
+            Self::OutOfToleranceGrace => "hardware_grace",
+            Self::NonGenuineGrace => "non_genuine_grace",
+            Self::Notification => "notification_mode",
+            Self::ExtendedGrace => "extended_grace"
+        }))
+    }
+}
+
+#[derive(Serialize)]
+pub struct OS {
+    /// `None` when the software licensing service cannot be queried.
+    pub ActivationStatus: Option<ActivationStatus>,
+    /// The build number, like `22631`.
+    pub CurrentBuild: String,
+    /// The feature update, like `23H2`.
+    pub DisplayVersion: String,
+    /// The edition, like `Professional`.
+    pub EditionID: String,
+    /// The time windows was installed or last upgraded to a new feature update, in local time.
+    pub InstallDate: DateTime<Local>,
+    /// `64-bit` or `32-bit`, in the language of the system.
+    pub OSArchitecture: String,
+    /// The product name, corrected to `Windows 11` on the builds that still report `Windows 10`.
+    pub ProductName: String
+}
+
+impl OS {
+    pub fn GetOperatingSystemVersion() -> WinfetchResult<Self> {
+        let product_name = registry::__InternalsRegistryGetString(RegistryHive::LocalMachine, CURRENT_VERSION_KEY, "ProductName")?;
+        let display_version = registry::__InternalsRegistryGetString(RegistryHive::LocalMachine, CURRENT_VERSION_KEY, "DisplayVersion")?;
+        let current_build = registry::__InternalsRegistryGetString(RegistryHive::LocalMachine, CURRENT_VERSION_KEY, "CurrentBuild")?;
+        let edition_id = registry::__InternalsRegistryGetString(RegistryHive::LocalMachine, CURRENT_VERSION_KEY, "EditionID")?;
+
+        let install_date = match registry::__InternalsRegistryGetDword(RegistryHive::LocalMachine, CURRENT_VERSION_KEY, "InstallDate") {
+            Ok(timestamp) => Local.timestamp_opt(timestamp as i64, 0)
+                .single()
+                .ok_or_else(|| WinfetchError(format!("invalid install date timestamp: {}", timestamp)))?,
+            Err(_) => Self::GetInstallDateFromWmi()?
+        };
+
+        let product_name = CorrectProductName(&product_name, current_build.parse().unwrap_or(0));
+
+        Ok(Self {
+            ActivationStatus: None,
+            CurrentBuild: current_build,
+            DisplayVersion: display_version,
+            EditionID: edition_id,
+            InstallDate: install_date,
+            OSArchitecture: String::new(),
+            ProductName: product_name
+        })
+    }
+
+    fn GetInstallDateFromWmi() -> WinfetchResult<DateTime<Local>> {
+        let rows = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT InstallDate FROM Win32_OperatingSystem", &["InstallDate"])?;
+        let row = rows.first().ok_or_else(|| WinfetchError(String::from("`Win32_OperatingSystem` returned no rows")))?;
+
+        let install_date = wmi::__InternalsWmiGet(row, "InstallDate")?
+            .AsString()
+            .ok_or_else(|| WinfetchError(String::from("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))?;
+
+        Ok(__internals::__InternalsParseCIMDateTime(install_date)?.with_timezone(&Local))
+    }
+
+    pub fn GetOperatingSystemArchitecture(&mut self) -> WinfetchResult<()> {
+        let rows = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT OSArchitecture FROM Win32_OperatingSystem", &["OSArchitecture"])?;
+        let row = rows.first().ok_or_else(|| WinfetchError(String::from("`Win32_OperatingSystem` returned no rows")))?;
+
+        let os_architecture = wmi::__InternalsWmiGet(row, "OSArchitecture")?
+            .AsString()
+            .ok_or_else(|| WinfetchError(String::from("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))?;
+
+        self.OSArchitecture.push_str(os_architecture);
+
+        Ok(())
+    }
+
+    pub fn GetActivationStatus(&mut self) -> WinfetchResult<()> {
+        let rows = wmi::__InternalsWmiQuery(
+            wmi::ROOT_CIMV2,
+            &format!("SELECT LicenseStatus FROM SoftwareLicensingProduct WHERE ApplicationID = '{}' AND PartialProductKey IS NOT NULL", WINDOWS_APPLICATION_ID),
+            &["LicenseStatus"]
+        )?;
+
+        // more than one product key may be installed; windows is activated as long as one of them is licensed
+        let license_statuses = rows.iter()
+            .filter_map(|row| row.get("LicenseStatus").and_then(WmiValue::AsInteger))
+            .collect::<Vec<_>>();
+        let license_status = license_statuses.iter()
+            .find(|&&license_status| license_status == 1)
+            .or_else(|| license_statuses.first())
+            .ok_or_else(|| WinfetchError(String::from("no product key is installed for windows")))?;
+
+        self.ActivationStatus = Some(ActivationStatus::FromLicenseStatus(*license_status)?);
+
+        Ok(())
+    }
+}
+
+pub fn CorrectProductName(product_name: &str, current_build: u32) -> String {
+    if current_build >= WINDOWS_11_FIRST_BUILD && product_name.starts_with("Windows 10") {
+        product_name.replacen("Windows 10", "Windows 11", 1)
+    }
+    else {
+        product_name.to_string()
+    }
+}
+
+impl fmt::Display for OS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, Version {} (Build {}) [{}]",
+            self.ProductName,
+            self.DisplayVersion,
+            self.CurrentBuild,
+            self.OSArchitecture
+        )
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    fmt,
+    time::Duration
+};
+
+#[cfg(windows)]
+use std::mem;
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::minwindef::{
+        FALSE,
+        FILETIME,
+        TRUE
+    },
+    um::{
+        handleapi::{
+            CloseHandle as Win32_CloseHandle,
+            INVALID_HANDLE_VALUE
+        },
+        processthreadsapi::{
+            GetProcessTimes as Win32_GetProcessTimes,
+            OpenProcess as Win32_OpenProcess
+        },
+        psapi::{
+            GetProcessMemoryInfo as Win32_GetProcessMemoryInfo,
+            PROCESS_MEMORY_COUNTERS
+        },
+        tlhelp32::{
+            CreateToolhelp32Snapshot as Win32_CreateToolHelp32Snapshot,
+            Process32FirstW as Win32_Process32FirstW,
+            Process32NextW as Win32_Process32NextW,
+            PROCESSENTRY32W,
+            TH32CS_SNAPPROCESS
+        },
+        winnt::PROCESS_QUERY_LIMITED_INFORMATION
+    }
+};
+
+use crate::winfetch::{
+    catalog,
+    dump::{
+        self,
+        RecordKey
+    },
+    error::WinfetchResult
+};
+
+#[cfg(windows)]
+use crate::winfetch::error::WinfetchError;
+
+pub const DEFAULT_TOP_PROCESSES: usize = 3;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum ProcessSort {
+    #[serde(rename = "cpu")]
+    Cpu,
+    #[serde(rename = "memory")]
+    Memory
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ProcessInfo {
+    pub ProcessId: u32,
+    pub Name: String,
+    pub Threads: u32,
+    /// The physical memory in use by the process, in bytes.
+    pub WorkingSet: u64,
+    /// The kernel and user time the process has used since it started.
+    pub CpuTime: Duration
+}
+
+impl ProcessInfo {
+    // the working set and the cpu time of protected processes cannot be read, so they are left at zero
+    #[cfg(windows)]
+    fn GetProcessDetails(&mut self) {
+        let handle = unsafe { Win32_OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, self.ProcessId) };
+
+        if handle.is_null() {
+            return;
+        }
+
+        let mut memory_counters: PROCESS_MEMORY_COUNTERS = unsafe { mem::zeroed() };
+        memory_counters.cb = mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+
+        if unsafe { Win32_GetProcessMemoryInfo(handle, &mut memory_counters, memory_counters.cb) } == TRUE {
+            self.WorkingSet = memory_counters.WorkingSetSize as u64;
+        }
+
+        let mut times = [FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 }; 4];
+        let [creation, exit, kernel, user] = &mut times;
+
+        if unsafe { Win32_GetProcessTimes(handle, creation, exit, kernel, user) } == TRUE {
+            let ticks = |time: &FILETIME| (time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64;
+
+            self.CpuTime = Duration::from_nanos((ticks(kernel) + ticks(user)) * 100);
+        }
+
+        unsafe { Win32_CloseHandle(handle) };
+    }
+}
+
+// walks the process snapshot; only the id, the name and the thread count are filled in
+pub fn EnumerateProcesses() -> WinfetchResult<Vec<ProcessInfo>> {
+    dump::Intercept(RecordKey::Call("EnumerateProcesses"), EnumerateProcessesLive)
+}
+
+// like `EnumerateProcesses`, with the working set and the cpu time of every process
+pub fn EnumerateProcessDetails() -> WinfetchResult<Vec<ProcessInfo>> {
+    dump::Intercept(RecordKey::Call("EnumerateProcessDetails"), EnumerateProcessDetailsLive)
+}
+
+#[cfg(not(windows))]
+fn EnumerateProcessesLive() -> WinfetchResult<Vec<ProcessInfo>> {
+    Err(crate::winfetch::__internals::__InternalsUnsupportedPlatform("the process snapshot"))
+}
+
+#[cfg(not(windows))]
+fn EnumerateProcessDetailsLive() -> WinfetchResult<Vec<ProcessInfo>> {
+    Err(crate::winfetch::__internals::__InternalsUnsupportedPlatform("the process snapshot"))
+}
+
+#[cfg(windows)]
+fn EnumerateProcessDetailsLive() -> WinfetchResult<Vec<ProcessInfo>> {
+    let mut processes = EnumerateProcessesLive()?;
+    processes.iter_mut().for_each(ProcessInfo::GetProcessDetails);
+
+    Ok(processes)
+}
+
+#[cfg(windows)]
+fn EnumerateProcessesLive() -> WinfetchResult<Vec<ProcessInfo>> {
+    let handle = unsafe { Win32_CreateToolHelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+
+    if handle == INVALID_HANDLE_VALUE {
+        return Err(WinfetchError(String::from("could not create process snapshot")));
+    }
+
+    let mut processes = Vec::new();
+    let mut entry: PROCESSENTRY32W = unsafe { mem::zeroed() };
+    entry.dwSize = mem::size_of::<PROCESSENTRY32W>() as u32;
+
+    let mut found = unsafe { Win32_Process32FirstW(handle, &mut entry) } == TRUE;
+
+    while found {
+        let length = entry.szExeFile.iter().position(|&char| char == 0).unwrap_or(entry.szExeFile.len());
+
+        processes.push(ProcessInfo {
+            ProcessId: entry.th32ProcessID,
+            Name: String::from_utf16_lossy(&entry.szExeFile[..length]),
+            Threads: entry.cntThreads,
+            ..ProcessInfo::default()
+        });
+
+        found = unsafe { Win32_Process32NextW(handle, &mut entry) } == TRUE;
+    }
+
+    unsafe { Win32_CloseHandle(handle) };
+
+    Ok(processes)
+}
+
+pub fn TotalThreads(processes: &[ProcessInfo]) -> u64 {
+    processes.iter().map(|process| process.Threads as u64).sum()
+}
+
+// the `count` heaviest processes, ties broken by name so the order is stable between refreshes; the system idle process,
+// whose cpu time is the time the processors were idle, is left out
+pub fn TopProcesses(processes: &[ProcessInfo], sort: ProcessSort, count: usize) -> Vec<ProcessInfo> {
+    let mut processes = processes.iter().filter(|process| process.ProcessId != 0).cloned().collect::<Vec<_>>();
+
+    processes.sort_by(|a, b| {
+        let ordering = match sort {
+            ProcessSort::Cpu => b.CpuTime.cmp(&a.CpuTime),
+            ProcessSort::Memory => b.WorkingSet.cmp(&a.WorkingSet)
+        };
+
+        ordering.then_with(|| a.Name.cmp(&b.Name))
+    });
+    processes.truncate(count);
+
+    processes
+}
+
+#[derive(Serialize)]
+pub struct Processes {
+    /// The number of running processes.
+    pub Count: u64,
+    /// The number of threads of all the processes together.
+    pub Threads: u64,
+    /// The heaviest processes, heaviest first.
+    pub Top: Vec<ProcessInfo>
+}
+
+impl Processes {
+    pub fn GetProcesses(sort: ProcessSort, count: usize) -> WinfetchResult<Self> {
+        let processes = EnumerateProcessDetails()?;
+
+        Ok(Self {
+            Count: processes.len() as u64,
+            Threads: TotalThreads(&processes),
+            Top: TopProcesses(&processes, sort, count)
+        })
+    }
+}
+
+pub fn FormatCpuTime(cpu_time: Duration) -> String {
+    let seconds = cpu_time.as_secs();
+
+    if seconds >= 3600 {
+        format!("{}h {}m", seconds / 3600, seconds / 60 % 60)
+    }
+    else if seconds >= 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    }
+    else {
+        format!("{:.1}s", cpu_time.as_secs_f64())
+    }
+}
+
+impl fmt::Display for ProcessInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}, {:.0} MB)", self.Name, FormatCpuTime(self.CpuTime), self.WorkingSet as f64 / 1024.0f64.powi(2))
+    }
+}
+
+impl fmt::Display for Processes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.Top.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "), catalog::Plural("thread", self.Threads))
+    }
+}
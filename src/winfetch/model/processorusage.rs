@@ -1,288 +1,239 @@
-use std::{
-    ffi::OsStr,
-    fmt,
-    mem,
-    os::windows::ffi::OsStrExt,
-    ptr
-};
-
-use winapi::{
-    shared::{
-        minwindef::TRUE,
-        ntdef::NULL,
-        rpcdce::{
-            RPC_C_AUTHN_LEVEL_CALL,
-            RPC_C_AUTHN_WINNT,
-            RPC_C_AUTHZ_NONE,
-            RPC_C_IMP_LEVEL_IMPERSONATE
-        },
-        winerror::S_OK,
-        wtypes::VT_I4,
-        wtypesbase::CLSCTX_INPROC_SERVER
-    },
-    um::{
-        combaseapi::{
-            CoCreateInstance as Win32_CoCreateInstance,
-            CoSetProxyBlanket as Win32_CoSetProxyBlanket,
-            CoUninitialize as Win32_CoUninitialize
-        },
-        oaidl::VARIANT,
-        objbase::CoInitialize as Win32_CoInitialize,
-        objidl::EOAC_NONE,
-        oleauto::VariantClear as Win32_VariantClear,
-        tlhelp32::{
-            CreateToolhelp32Snapshot as Win32_CreateToolHelp32Snapshot,
-            Process32First as Win32_Process32First,
-            Process32Next as Win32_Process32Next,
-            PROCESSENTRY32,
-            TH32CS_SNAPPROCESS
-        },
-        wbemcli::{
-            CLSID_WbemLocator,
-            IEnumWbemClassObject,
-            IID_IWbemLocator,
-            IWbemClassObject,
-            IWbemLocator,
-            IWbemServices,
-            WBEM_FLAG_FORWARD_ONLY,
-            WBEM_FLAG_RETURN_IMMEDIATELY,
-            WBEM_INFINITE
-        }
-    }
-};
-
-use crate::winfetch::{
-    error::{
-        WinfetchError,
-        WinfetchResult,
-    },
-    utils
-};
-
-pub struct ProcessorUsage {
-    LoadPercentage: i32,
-    Processes: u64
-}
-
-impl ProcessorUsage {
-    pub fn GetProcessorLoadPercentage() -> WinfetchResult<Self> {
-        let mut wbem_locator_c_void = NULL;
-
-        unsafe {
-            match Win32_CoInitialize(
-                NULL
-            ) {
-                S_OK => (),
-                error_code => return Err(WinfetchError(format!("failed to initialize com library; error code: {}", error_code)))
-            }
-
-            match Win32_CoCreateInstance(
-                &CLSID_WbemLocator,
-                ptr::null_mut(),
-                CLSCTX_INPROC_SERVER,
-                &IID_IWbemLocator,
-                &mut wbem_locator_c_void
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to create wbem locator; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_locator = ptr::NonNull::new(wbem_locator_c_void as *mut IWbemLocator).unwrap();
-        let mut root_cimv2 = AsRef::<OsStr>::as_ref(r"ROOT\CIMV2")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut wbem_service_null = ptr::null_mut::<IWbemServices>();
-
-        unsafe {
-            match (*wbem_locator.as_ptr()).ConnectServer(
-                root_cimv2.as_mut_ptr(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                0,
-                ptr::null_mut(),
-                ptr::null_mut(),
-                &mut wbem_service_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to connect to wbem server; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_service_nonnull = ptr::NonNull::new(wbem_service_null).unwrap();
-
-        unsafe {
-            match Win32_CoSetProxyBlanket(
-                wbem_service_nonnull.as_ptr() as _,
-                RPC_C_AUTHN_WINNT,
-                RPC_C_AUTHZ_NONE,
-                ptr::null_mut(),
-                RPC_C_AUTHN_LEVEL_CALL,
-                RPC_C_IMP_LEVEL_IMPERSONATE,
-                NULL,
-                EOAC_NONE
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to set proxy; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut wql = AsRef::<OsStr>::as_ref(r"WQL")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let mut processor_query = AsRef::<OsStr>::as_ref(r"SELECT * FROM Win32_Processor")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut enum_wbem_class_object_null = NULL as *mut IEnumWbemClassObject;
-
-        unsafe {
-            match (*wbem_service_nonnull.as_ptr()).ExecQuery(
-                wql.as_mut_ptr(),
-                processor_query.as_mut_ptr(),
-                (WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY) as i32,
-                ptr::null_mut(),
-                &mut enum_wbem_class_object_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to execute query; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut wbem_class_object_null = NULL as *mut IWbemClassObject;
-        let mut return_value = 0;
-
-        unsafe {
-            match (*enum_wbem_class_object_null).Next(
-                WBEM_INFINITE as i32,
-                1,
-                &mut wbem_class_object_null,
-                &mut return_value
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("could not get next element of enumeration; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let load_percentage_property_name = AsRef::<OsStr>::as_ref(r"LoadPercentage")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let mut load_percentage_property = unsafe {
-            mem::zeroed::<VARIANT>()
-        };
-
-        unsafe {
-            match (*wbem_class_object_null).Get(
-                load_percentage_property_name.as_ptr(),
-                0,
-                &mut load_percentage_property,
-                ptr::null_mut(),
-                ptr::null_mut()
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("could not get `Name` property of wbem class object; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let load_percentage_variant_type = unsafe {
-            load_percentage_property.n1.n2().vt
-        };
-        let load_percentage_variant_value = unsafe {
-            match load_percentage_variant_type as u32 {
-                VT_I4 => {
-                    let int_ptr = load_percentage_property.n1.n2().n3.intVal();
-                    *int_ptr
-                },
-                _ => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("code branch should be unreachable - the variant type is expected to be `VT_I4`")))
-                }
-            }
-        };
-
-        unsafe {
-            Win32_VariantClear(&mut load_percentage_property);
-
-            Win32_CoUninitialize();
-        }
-
-        Ok(Self {
-            LoadPercentage: load_percentage_variant_value,
-            Processes: 0
-        })
-    }
-
-    pub fn GetProcessesCount(&mut self) -> WinfetchResult<()> {
-        let handle = unsafe {
-            Win32_CreateToolHelp32Snapshot(
-                TH32CS_SNAPPROCESS,
-                0
-            )
-        };
-        let mut count = 0;
-
-        if unsafe { Win32_Process32First(handle, &mut PROCESSENTRY32 {
-            dwSize: mem::size_of::<PROCESSENTRY32>() as u32,
-            cntUsage: 0,
-            th32ProcessID: 0,
-            th32DefaultHeapID: 0,
-            th32ModuleID: 0,
-            cntThreads: 0,
-            th32ParentProcessID: 0,
-            pcPriClassBase: 0,
-            dwFlags: 0,
-            szExeFile: [0i8; 260]
-        }) } == TRUE {
-            count += 1;
-        }
-
-        while unsafe { Win32_Process32Next(handle, &mut PROCESSENTRY32 {
-            dwSize: mem::size_of::<PROCESSENTRY32>() as u32,
-            cntUsage: 0,
-            th32ProcessID: 0,
-            th32DefaultHeapID: 0,
-            th32ModuleID: 0,
-            cntThreads: 0,
-            th32ParentProcessID: 0,
-            pcPriClassBase: 0,
-            dwFlags: 0,
-            szExeFile: [0i8; 260]
-        }) } == TRUE {
-            count += 1;
-        }
-
-        self.Processes = count;
-
-        Ok(())
-    }
-}
-
-impl fmt::Display for ProcessorUsage {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} processes", utils::GeneratePercentageBar(self.LoadPercentage).unwrap(), self.Processes)
-    }
+use std::{
+    fmt,
+    thread,
+    time::Duration
+};
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::minwindef::FILETIME,
+    um::{
+        errhandlingapi::GetLastError as Win32_GetLastError,
+        processthreadsapi::GetSystemTimes as Win32_GetSystemTimes
+    }
+};
+
+use crate::winfetch::{
+    __internals::wmi,
+    catalog,
+    dump::{
+        self,
+        RecordKey
+    },
+    error::WinfetchResult,
+    model::processes,
+    utils
+};
+
+#[cfg(windows)]
+use crate::winfetch::error::WinfetchError;
+
+// the time spent by all processors since boot, in 100 nanosecond intervals; the kernel time includes the idle time
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProcessorTimes {
+    pub Idle: u64,
+    pub Kernel: u64,
+    pub User: u64
+}
+
+impl ProcessorTimes {
+    pub fn GetProcessorTimes() -> WinfetchResult<Self> {
+        dump::Intercept(RecordKey::Call("GetSystemTimes"), Self::GetProcessorTimesLive)
+    }
+
+    #[cfg(not(windows))]
+    fn GetProcessorTimesLive() -> WinfetchResult<Self> {
+        Err(crate::winfetch::__internals::__InternalsUnsupportedPlatform("GetSystemTimes"))
+    }
+
+    #[cfg(windows)]
+    fn GetProcessorTimesLive() -> WinfetchResult<Self> {
+        let mut idle = FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 };
+        let mut kernel = FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 };
+        let mut user = FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 };
+
+        if unsafe { Win32_GetSystemTimes(&mut idle, &mut kernel, &mut user) } == 0 {
+            return Err(WinfetchError(format!("could not get system times; error code: {}", unsafe { Win32_GetLastError() })));
+        }
+
+        let ticks = |time: FILETIME| (time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64;
+
+        Ok(Self {
+            Idle: ticks(idle),
+            Kernel: ticks(kernel),
+            User: ticks(user)
+        })
+    }
+}
+
+// the raw counters of one `Win32_PerfRawData_Counters_ProcessorInformation` instance; `IdleTime` and `Timestamp` are in
+// 100 nanosecond intervals
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProcessorCounter {
+    pub Name: String,
+    pub IdleTime: u64,
+    pub Timestamp: u64
+}
+
+impl ProcessorCounter {
+    pub fn GetProcessorCounters() -> WinfetchResult<Vec<Self>> {
+        let rows = wmi::__InternalsWmiQuery(
+            wmi::ROOT_CIMV2,
+            "SELECT Name, PercentProcessorTime, Timestamp_Sys100NS FROM Win32_PerfRawData_Counters_ProcessorInformation",
+            &["Name", "PercentProcessorTime", "Timestamp_Sys100NS"]
+        )?;
+
+        // the 64 bit counters are returned as strings, which `AsInteger` parses
+        Ok(rows
+            .iter()
+            .filter_map(|row| Some(Self {
+                Name: row.get("Name")?.AsString()?.to_string(),
+                IdleTime: row.get("PercentProcessorTime")?.AsInteger()? as u64,
+                Timestamp: row.get("Timestamp_Sys100NS")?.AsInteger()? as u64
+            }))
+            .collect())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ProcessorLoad {
+    /// `<socket>,<core>` for a core, and the socket number for a socket.
+    pub Name: String,
+    /// From 0 to 100.
+    pub LoadPercentage: f64
+}
+
+#[derive(Serialize)]
+pub struct ProcessorUsage {
+    /// The load of all the processors over the sample interval, from 0 to 100.
+    pub LoadPercentage: f64,
+    /// In numeric order; empty when the per-core counters cannot be queried.
+    pub Cores: Vec<ProcessorLoad>,
+    /// In numeric order; empty when the per-core counters cannot be queried.
+    pub Sockets: Vec<ProcessorLoad>,
+    /// The number of running processes; 0 until `GetProcessesCount` is called.
+    pub Processes: u64
+}
+
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+// the share of the time between two samples the processors were not idle, in percents
+pub fn LoadBetween(before: &ProcessorTimes, after: &ProcessorTimes) -> f64 {
+    let total = (after.Kernel + after.User).saturating_sub(before.Kernel + before.User);
+    let idle = after.Idle.saturating_sub(before.Idle).min(total);
+
+    if total == 0 {
+        return 0.0;
+    }
+
+    (total - idle) as f64 / total as f64 * 100.0
+}
+
+// splits two samples of the processor counters into the load of every core and of every socket; instances are named
+// `<socket>,<core>` and `<socket>,_Total`, and the `_Total` instance is left out since `GetSystemTimes` covers it
+pub fn CounterLoadsBetween(before: &[ProcessorCounter], after: &[ProcessorCounter]) -> (Vec<ProcessorLoad>, Vec<ProcessorLoad>) {
+    let mut cores = Vec::new();
+    let mut sockets = Vec::new();
+
+    for after in after {
+        let before = match before.iter().find(|before| before.Name == after.Name) {
+            Some(before) => before,
+            None => continue
+        };
+
+        let elapsed = after.Timestamp.saturating_sub(before.Timestamp);
+        let idle = after.IdleTime.saturating_sub(before.IdleTime).min(elapsed);
+        let load = ProcessorLoad {
+            Name: after.Name.clone(),
+            LoadPercentage: if elapsed == 0 { 0.0 } else { (elapsed - idle) as f64 / elapsed as f64 * 100.0 }
+        };
+
+        match after.Name.split_once(',') {
+            Some((socket, "_Total")) => sockets.push(ProcessorLoad { Name: socket.to_string(), ..load }),
+            Some(_) => cores.push(load),
+            None => ()
+        }
+    }
+
+    // the instances are not returned in numeric order
+    let key = |load: &ProcessorLoad| load.Name.split(',').map(|part| part.parse::<u32>().unwrap_or(u32::MAX)).collect::<Vec<_>>();
+    cores.sort_by_key(key);
+    sockets.sort_by_key(key);
+
+    (cores, sockets)
+}
+
+impl ProcessorUsage {
+    // samples the processor times twice, `sample_interval` apart; the per-core counters are left out when they cannot be queried
+    pub fn GetProcessorUsage(sample_interval: Duration) -> WinfetchResult<Self> {
+        let times_before = ProcessorTimes::GetProcessorTimes()?;
+        let counters_before = ProcessorCounter::GetProcessorCounters().unwrap_or_default();
+
+        // a replayed dump already holds both samples
+        if !dump::IsReplaying() {
+            thread::sleep(sample_interval);
+        }
+
+        let times_after = ProcessorTimes::GetProcessorTimes()?;
+        let counters_after = ProcessorCounter::GetProcessorCounters().unwrap_or_default();
+        let (cores, sockets) = CounterLoadsBetween(&counters_before, &counters_after);
+
+        Ok(Self {
+            LoadPercentage: LoadBetween(&times_before, &times_after),
+            Cores: cores,
+            Sockets: sockets,
+            Processes: 0
+        })
+    }
+
+    pub fn GetProcessesCount(&mut self) -> WinfetchResult<()> {
+        self.Processes = processes::EnumerateProcesses()?.len() as u64;
+
+        Ok(())
+    }
+}
+
+impl ProcessorUsage {
+    // one mini bar per core, with the sockets separated by spaces
+    pub fn CoreBars(&self) -> String {
+        let mut bars = String::new();
+        let mut current_socket = None;
+
+        for core in &self.Cores {
+            let socket = core.Name.split(',').next();
+
+            if current_socket.is_some() && current_socket != socket {
+                bars.push(' ');
+            }
+
+            current_socket = socket;
+            bars.push_str(&utils::GenerateMiniBar(core.LoadPercentage));
+        }
+
+        bars
+    }
+}
+
+impl fmt::Display for ProcessorUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let load_percentage = self.LoadPercentage.round().clamp(0.0, 100.0);
+
+        write!(f, "{} {}%", utils::GeneratePercentageBar(load_percentage as i32), load_percentage)?;
+
+        // the total is followed by the load of every socket on multi-socket machines
+        if self.Sockets.len() > 1 {
+            write!(
+                f,
+                " ({})",
+                self.Sockets.iter().map(|socket| format!("socket {}: {:.0}%", socket.Name, socket.LoadPercentage)).collect::<Vec<_>>().join(", ")
+            )?;
+        }
+
+        write!(f, " {}", catalog::Plural("process", self.Processes))
+    }
 }
\ No newline at end of file
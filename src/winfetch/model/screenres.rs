@@ -1,80 +1,280 @@
-use std::fmt;
-
-use winapi::{
-    shared::{
-        minwindef::{
-            BOOL,
-            LPARAM
-        },
-        ntdef::NULL,
-        windef::{
-            HDC,
-            HMONITOR,
-            LPRECT,
-        }
-    },
-    um::{
-        wingdi::{
-            GetDeviceCaps as Win32_GetDeviceCaps,
-            HORZRES,
-            VERTRES
-        },
-        winuser::{
-            EnumDisplayMonitors as Win32_EnumDisplayMonitors,
-            GetDC as Win32_GetDC,
-            ReleaseDC as Win32_ReleaseDC
-        }
-    }
-};
-
-use crate::winfetch::error::{
-    WinfetchError,
-    WinfetchResult
-};
-
-pub struct ScreenResolution {
-    Resolutions: Vec<(i32, i32)>
-}
-
-impl ScreenResolution {
-    pub fn GetScreenResolution() -> WinfetchResult<Self> {
-        let mut resolutions = Vec::new();
-
-        unsafe {
-            let hdc = Win32_GetDC(NULL as *mut _);
-
-            if Win32_EnumDisplayMonitors(
-                hdc,
-                NULL as *mut _,
-                Some(MonitorEnumProc),
-                &mut resolutions as *mut Vec<(i32, i32)> as isize
-            ) == 0 {
-                return Err(WinfetchError(String::from("enum display monitors returned a zero exit code")));
-            }
-
-            Win32_ReleaseDC(NULL as *mut _, hdc);
-        }
-
-        Ok(Self {
-            Resolutions: resolutions
-        })
-    }
-}
-
-impl fmt::Display for ScreenResolution {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let resolutions = self.Resolutions.iter().map(|(horizontal, vertical)| format!("{}x{}", horizontal, vertical)).collect::<Vec<_>>();
-
-        write!(f, "{}", resolutions.join(", "))
-    }
-}
-
-#[no_mangle]
-pub(in self) unsafe extern "system" fn MonitorEnumProc(_: HMONITOR, hdc: HDC, _: LPRECT, resolutions: LPARAM) -> BOOL {
-    let horizontal_resolution = Win32_GetDeviceCaps(hdc, HORZRES);
-    let vertical_resolution = Win32_GetDeviceCaps(hdc, VERTRES);
-
-    (*(resolutions as *mut Vec<(i32, i32)>)).push((horizontal_resolution, vertical_resolution));
-
-    1
-}
+use std::fmt;
+
+#[cfg(windows)]
+use std::{
+    mem,
+    ptr
+};
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::{
+        minwindef::{
+            BOOL,
+            LPARAM,
+            TRUE
+        },
+        windef::{
+            DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
+            HDC,
+            HMONITOR,
+            LPRECT
+        },
+        winerror::S_OK
+    },
+    um::{
+        shellscalingapi::{
+            GetDpiForMonitor as Win32_GetDpiForMonitor,
+            MDT_EFFECTIVE_DPI
+        },
+        wingdi::{
+            DEVMODEW,
+            DISPLAY_DEVICEW
+        },
+        winuser::{
+            EnumDisplayDevicesW as Win32_EnumDisplayDevicesW,
+            EnumDisplayMonitors as Win32_EnumDisplayMonitors,
+            EnumDisplaySettingsW as Win32_EnumDisplaySettingsW,
+            GetMonitorInfoW as Win32_GetMonitorInfoW,
+            SetThreadDpiAwarenessContext as Win32_SetThreadDpiAwarenessContext,
+            EDD_GET_DEVICE_INTERFACE_NAME,
+            ENUM_CURRENT_SETTINGS,
+            MONITORINFO,
+            MONITORINFOEXW,
+            MONITORINFOF_PRIMARY
+        }
+    }
+};
+
+use crate::winfetch::{
+    __internals::wmi::{
+        self,
+        WmiValue
+    },
+    catalog,
+    dump::{
+        self,
+        RecordKey
+    },
+    error::WinfetchResult
+};
+
+#[cfg(windows)]
+use crate::winfetch::error::WinfetchError;
+
+// the dpi at a scaling of 100%
+#[cfg(windows)]
+const DEFAULT_DPI: u32 = 96;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Monitor {
+    /// The model name from the edid, if the driver exposes it.
+    pub Name: Option<String>,
+    /// The gdi device name, like `\\.\DISPLAY1`.
+    pub DeviceName: String,
+    /// The device interface path, used to find the edid of the monitor.
+    pub DeviceId: String,
+    /// The physical horizontal resolution in pixels, unaffected by the scaling.
+    pub Width: u32,
+    /// The physical vertical resolution in pixels, unaffected by the scaling.
+    pub Height: u32,
+    /// In hertz; 0 or 1 when the driver uses the default rate.
+    pub RefreshRate: u32,
+    /// In percents, like `125`.
+    pub Scaling: u32,
+    pub Primary: bool
+}
+
+#[derive(Serialize)]
+pub struct ScreenResolution {
+    pub Monitors: Vec<Monitor>
+}
+
+impl ScreenResolution {
+    pub fn GetScreenResolution() -> WinfetchResult<Self> {
+        let mut monitors = dump::Intercept(RecordKey::Call("EnumDisplayMonitors"), EnumerateMonitors)?;
+
+        // the names are left out when the monitor drivers do not expose the edid through wmi
+        if let Ok(rows) = wmi::__InternalsWmiQuery(wmi::ROOT_WMI, "SELECT InstanceName, UserFriendlyName FROM WmiMonitorID", &["InstanceName", "UserFriendlyName"]) {
+            for monitor in &mut monitors {
+                monitor.Name = rows
+                    .iter()
+                    .find(|row| row
+                        .get("InstanceName")
+                        .and_then(|value| value.AsString())
+                        .is_some_and(|instance_name| MatchesInstanceName(&monitor.DeviceId, instance_name)))
+                    .and_then(|row| row.get("UserFriendlyName")?.AsArray())
+                    .and_then(DecodeFriendlyName);
+            }
+        }
+
+        // the primary monitor comes first
+        monitors.sort_by_key(|monitor| !monitor.Primary);
+
+        Ok(Self {
+            Monitors: monitors
+        })
+    }
+
+    pub fn Primary(&self) -> Option<&Monitor> {
+        self.Monitors.iter().find(|monitor| monitor.Primary)
+    }
+}
+
+// `UserFriendlyName` holds one character per element, padded with zeroes
+pub fn DecodeFriendlyName(values: &[WmiValue]) -> Option<String> {
+    let name = values
+        .iter()
+        .map_while(|value| value.AsInteger().filter(|&char| char != 0))
+        .filter_map(|char| char::from_u32(char as u32))
+        .collect::<String>();
+
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+// `DISPLAY\GSM5B7F\4&2f2f6c4a&0&UID4352_0` names the monitor with the interface path
+// `\\?\DISPLAY#GSM5B7F#4&2f2f6c4a&0&UID4352#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}`
+pub fn MatchesInstanceName(device_id: &str, instance_name: &str) -> bool {
+    let instance_name = match instance_name.rsplit_once('_') {
+        Some((instance_name, index)) if index.chars().all(|char| char.is_ascii_digit()) => instance_name,
+        _ => instance_name
+    };
+
+    !instance_name.is_empty() && device_id.to_ascii_uppercase().contains(&format!("{}#", instance_name.replace('\\', "#").to_ascii_uppercase()))
+}
+
+#[cfg(not(windows))]
+fn EnumerateMonitors() -> WinfetchResult<Vec<Monitor>> {
+    Err(crate::winfetch::__internals::__InternalsUnsupportedPlatform("EnumDisplayMonitors"))
+}
+
+#[cfg(windows)]
+fn EnumerateMonitors() -> WinfetchResult<Vec<Monitor>> {
+    let mut handles: Vec<HMONITOR> = Vec::new();
+
+    unsafe {
+        // without per monitor awareness, every monitor reports the scaling of the primary one
+        let previous_context = Win32_SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2);
+
+        let monitors = if Win32_EnumDisplayMonitors(
+            ptr::null_mut(),
+            ptr::null(),
+            Some(MonitorEnumProc),
+            &mut handles as *mut Vec<HMONITOR> as LPARAM
+        ) == 0 {
+            Err(WinfetchError(String::from("enum display monitors returned a zero exit code")))
+        }
+        else {
+            Ok(handles.into_iter().filter_map(|handle| ReadMonitor(handle)).collect())
+        };
+
+        if !previous_context.is_null() {
+            Win32_SetThreadDpiAwarenessContext(previous_context);
+        }
+
+        monitors
+    }
+}
+
+#[cfg(windows)]
+fn FromWide(wide: &[u16]) -> String {
+    let length = wide.iter().position(|&char| char == 0).unwrap_or(wide.len());
+
+    String::from_utf16_lossy(&wide[..length])
+}
+
+#[cfg(windows)]
+unsafe fn ReadMonitor(handle: HMONITOR) -> Option<Monitor> {
+    let mut info: MONITORINFOEXW = mem::zeroed();
+    info.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
+
+    if Win32_GetMonitorInfoW(handle, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO) == 0 {
+        return None;
+    }
+
+    let mut mode: DEVMODEW = mem::zeroed();
+    mode.dmSize = mem::size_of::<DEVMODEW>() as u16;
+
+    if Win32_EnumDisplaySettingsW(info.szDevice.as_ptr(), ENUM_CURRENT_SETTINGS, &mut mode) == 0 {
+        return None;
+    }
+
+    let mut dpi_x = 0;
+    let mut dpi_y = 0;
+
+    if Win32_GetDpiForMonitor(handle, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) != S_OK {
+        dpi_x = DEFAULT_DPI;
+    }
+
+    let mut device: DISPLAY_DEVICEW = mem::zeroed();
+    device.cb = mem::size_of::<DISPLAY_DEVICEW>() as u32;
+
+    let device_id = if Win32_EnumDisplayDevicesW(info.szDevice.as_ptr(), 0, &mut device, EDD_GET_DEVICE_INTERFACE_NAME) != 0 {
+        FromWide(&device.DeviceID)
+    }
+    else {
+        String::new()
+    };
+
+    Some(Monitor {
+        Name: None,
+        DeviceName: FromWide(&info.szDevice),
+        DeviceId: device_id,
+        Width: mode.dmPelsWidth,
+        Height: mode.dmPelsHeight,
+        RefreshRate: mode.dmDisplayFrequency,
+        Scaling: (dpi_x * 100 + DEFAULT_DPI / 2) / DEFAULT_DPI,
+        Primary: info.dwFlags & MONITORINFOF_PRIMARY != 0
+    })
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn MonitorEnumProc(handle: HMONITOR, _: HDC, _: LPRECT, handles: LPARAM) -> BOOL {
+    (*(handles as *mut Vec<HMONITOR>)).push(handle);
+
+    TRUE
+}
+
+impl Monitor {
+    // `2560x1440 @ 144Hz (125%)`, without the name and the primary flag
+    pub fn Mode(&self) -> String {
+        let mut mode = format!("{}x{}", self.Width, self.Height);
+
+        if self.RefreshRate > 1 {
+            mode.push_str(&format!(" @ {}Hz", self.RefreshRate));
+        }
+
+        if self.Scaling > 0 {
+            mode.push_str(&format!(" ({}%)", self.Scaling));
+        }
+
+        mode
+    }
+}
+
+impl fmt::Display for Monitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = &self.Name {
+            write!(f, "{} ", name)?;
+        }
+
+        write!(f, "{}", self.Mode())?;
+
+        if self.Primary {
+            write!(f, " [{}]", catalog::Message("primary"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ScreenResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.Monitors.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))
+    }
+}
@@ -1,385 +1,300 @@
-use std::{
-    ffi::{
-        OsStr,
-        OsString
-    },
-    fmt,
-    mem,
-    os::windows::ffi::{
-        OsStrExt,
-        OsStringExt
-    },
-    ptr,
-    slice
-};
-
-use winapi::{
-    shared::{
-        ntdef::NULL,
-        rpcdce::{
-            RPC_C_AUTHN_LEVEL_CALL,
-            RPC_C_AUTHN_WINNT,
-            RPC_C_AUTHZ_NONE,
-            RPC_C_IMP_LEVEL_IMPERSONATE
-        },
-        winerror::{
-            HRESULT,
-            S_OK,
-        },
-        wtypes::VT_BSTR,
-        wtypesbase::CLSCTX_INPROC_SERVER
-    },
-    um::{
-        combaseapi::{
-            CoCreateInstance as Win32_CoCreateInstance,
-            CoSetProxyBlanket as Win32_CoSetProxyBlanket,
-            CoUninitialize as Win32_CoUninitialize
-        },
-        oaidl::VARIANT,
-        objbase::CoInitialize as Win32_CoInitialize,
-        objidl::EOAC_NONE,
-        oleauto::VariantClear as Win32_VariantClear,
-        wbemcli::{
-            CLSID_WbemLocator,
-            IEnumWbemClassObject,
-            IID_IWbemLocator,
-            IWbemClassObject,
-            IWbemLocator,
-            IWbemServices,
-            WBEM_FLAG_FORWARD_ONLY,
-            WBEM_FLAG_RETURN_IMMEDIATELY,
-            WBEM_INFINITE,
-            WBEM_S_NO_ERROR,
-            WBEM_S_FALSE
-        }
-    }
-};
-
-use crate::winfetch::{
-    __internals,
-    error::{
-        WinfetchError,
-        WinfetchResult,
-    },
-    utils
-};
-
-pub struct Storage {
-    pub Drives: Vec<StorageDrive>
-}
-
-impl Storage {
-    pub fn GetStorageStatistics() -> WinfetchResult<Self> {
-        let mut wbem_locator_c_void = NULL;
-
-        unsafe {
-            match Win32_CoInitialize(
-                NULL
-            ) {
-                S_OK => (),
-                error_code => return Err(WinfetchError(format!("failed to initialize com library; error code: {}", error_code)))
-            }
-
-            match Win32_CoCreateInstance(
-                &CLSID_WbemLocator,
-                ptr::null_mut(),
-                CLSCTX_INPROC_SERVER,
-                &IID_IWbemLocator,
-                &mut wbem_locator_c_void
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to create wbem locator; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_locator = ptr::NonNull::new(wbem_locator_c_void as *mut IWbemLocator).unwrap();
-        let mut root_cimv2 = AsRef::<OsStr>::as_ref(r"ROOT\CIMV2")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut wbem_service_null = ptr::null_mut::<IWbemServices>();
-
-        unsafe {
-            match (*wbem_locator.as_ptr()).ConnectServer(
-                root_cimv2.as_mut_ptr(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                0,
-                ptr::null_mut(),
-                ptr::null_mut(),
-                &mut wbem_service_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to connect wbem server; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let wbem_service_nonnull = ptr::NonNull::new(wbem_service_null).unwrap();
-
-        unsafe {
-            match Win32_CoSetProxyBlanket(
-                wbem_service_nonnull.as_ptr() as _,
-                RPC_C_AUTHN_WINNT,
-                RPC_C_AUTHZ_NONE,
-                ptr::null_mut(),
-                RPC_C_AUTHN_LEVEL_CALL,
-                RPC_C_IMP_LEVEL_IMPERSONATE,
-                NULL,
-                EOAC_NONE
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to set proxy; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut wql = AsRef::<OsStr>::as_ref(r"WQL")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-        let mut logical_disk_query = AsRef::<OsStr>::as_ref(r"SELECT * FROM Win32_LogicalDisk")
-            .encode_wide()
-            .chain(Some(0).into_iter())
-            .collect::<Vec<u16>>();
-
-        let mut enum_wbem_class_object_null = NULL as *mut IEnumWbemClassObject;
-
-        unsafe {
-            match (*wbem_service_nonnull.as_ptr()).ExecQuery(
-                wql.as_mut_ptr(),
-                logical_disk_query.as_mut_ptr(),
-                (WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY) as i32,
-                ptr::null_mut(),
-                &mut enum_wbem_class_object_null
-            ) {
-                S_OK => (),
-                error_code => {
-                    Win32_CoUninitialize();
-                    return Err(WinfetchError(format!("failed to execute query; error code: {}", error_code)))
-                }
-            }
-        }
-
-        let mut hresult: HRESULT = WBEM_S_NO_ERROR as i32;
-        let mut vector = Vec::new();
-
-        while hresult == WBEM_S_NO_ERROR as i32 {
-            let mut wbem_class_object_null = NULL as *mut IWbemClassObject;
-            let mut return_value = 0;
-
-            hresult = unsafe {
-                (*enum_wbem_class_object_null).Next(
-                    WBEM_INFINITE as i32,
-                    1,
-                    &mut wbem_class_object_null,
-                    &mut return_value
-                )
-            };
-
-            if hresult == WBEM_S_FALSE as i32 || wbem_class_object_null.is_null() {
-                break;
-            }
-
-            let device_id_property_name = AsRef::<OsStr>::as_ref(r"DeviceID")
-                .encode_wide()
-                .chain(Some(0).into_iter())
-                .collect::<Vec<u16>>();
-            let mut device_id_property = unsafe {
-                mem::zeroed::<VARIANT>()
-            };
-
-            unsafe {
-                match (*wbem_class_object_null).Get(
-                    device_id_property_name.as_ptr(),
-                    0,
-                    &mut device_id_property,
-                    ptr::null_mut(),
-                    ptr::null_mut()
-                ) {
-                    S_OK => (),
-                    error_code => {
-                        Win32_CoUninitialize();
-                        return Err(WinfetchError(format!("could not get `DeviceID` property of wbem class object; error code: {}", error_code)))
-                    }
-                }
-            }
-
-            let device_id_variant_type = unsafe {
-                device_id_property.n1.n2().vt
-            };
-            let device_id_variant_value = unsafe {
-                match device_id_variant_type as u32 {
-                    VT_BSTR => {
-                        let bstr_ptr = device_id_property.n1.n2().n3.bstrVal();
-                        let mut i = 0;
-
-                        while *bstr_ptr.offset(i) != 0 {
-                            i += 1;
-                        }
-
-                        let slice = slice::from_raw_parts(*bstr_ptr, i as usize + 1);
-                        OsString::from(format!("/mnt/{}/", OsString::from_wide(slice).into_string().unwrap().replace('\0', "")).replace(":", "").to_ascii_lowercase())
-                    },
-                    _ => {
-                        Win32_CoUninitialize();
-                        return Err(WinfetchError(format!("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))
-                    }
-                }
-            };
-
-            let free_space_property_name = AsRef::<OsStr>::as_ref(r"FreeSpace")
-                .encode_wide()
-                .chain(Some(0).into_iter())
-                .collect::<Vec<u16>>();
-            let mut free_space_property = unsafe {
-                mem::zeroed::<VARIANT>()
-            };
-
-            unsafe {
-                match (*wbem_class_object_null).Get(
-                    free_space_property_name.as_ptr(),
-                    0,
-                    &mut free_space_property,
-                    ptr::null_mut(),
-                    ptr::null_mut()
-                ) {
-                    S_OK => (),
-                    error_code => {
-                        Win32_CoUninitialize();
-                        return Err(WinfetchError(format!("could not get `FreeSpace` property of wbem class object; error code: {}", error_code)))
-                    }
-                }
-            }
-
-            let free_space_variant_type = unsafe {
-                free_space_property.n1.n2().vt
-            };
-            let free_space_variant_value = unsafe {
-                match free_space_variant_type as u32 {
-                    VT_BSTR => {
-                        let bstr_ptr = free_space_property.n1.n2().n3.bstrVal();
-                        let mut i = 0;
-
-                        while *bstr_ptr.offset(i) != 0 {
-                            i += 1;
-                        }
-
-                        let slice = slice::from_raw_parts(*bstr_ptr, i as usize + 1);
-                        OsString::from(OsString::from_wide(slice).into_string().unwrap().replace('\0', ""))
-                    },
-                    _ => {
-                        Win32_CoUninitialize();
-                        return Err(WinfetchError(format!("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))
-                    }
-                }
-            };
-
-            let size_property_name = AsRef::<OsStr>::as_ref(r"Size")
-                .encode_wide()
-                .chain(Some(0).into_iter())
-                .collect::<Vec<u16>>();
-            let mut size_property = unsafe {
-                mem::zeroed::<VARIANT>()
-            };
-
-            unsafe {
-                match (*wbem_class_object_null).Get(
-                    size_property_name.as_ptr(),
-                    0,
-                    &mut size_property,
-                    ptr::null_mut(),
-                    ptr::null_mut()
-                ) {
-                    S_OK => (),
-                    error_code => {
-                        Win32_CoUninitialize();
-                        return Err(WinfetchError(format!("could not get `Size` property of wbem class object; error code: {}", error_code)))
-                    }
-                }
-            }
-
-            let size_variant_type = unsafe {
-                size_property.n1.n2().vt
-            };
-            let size_variant_value = unsafe {
-                match size_variant_type as u32 {
-                    VT_BSTR => {
-                        let bstr_ptr = size_property.n1.n2().n3.bstrVal();
-                        let mut i = 0;
-
-                        while *bstr_ptr.offset(i) != 0 {
-                            i += 1;
-                        }
-
-                        let slice = slice::from_raw_parts(*bstr_ptr, i as usize + 1);
-                        OsString::from(OsString::from_wide(slice).into_string().unwrap().replace('\0', ""))
-                    },
-                    _ => {
-                        Win32_CoUninitialize();
-                        return Err(WinfetchError(format!("code branch should be unreachable - the variant type is expected to be `VT_BSTR`")))
-                    }
-                }
-            };
-
-            vector.push(StorageDrive {
-                DriveLetter: device_id_variant_value,
-                FreeSpace: free_space_variant_value.into_string().unwrap().parse().unwrap(),
-                Size: size_variant_value.into_string().unwrap().parse().unwrap()
-            });
-
-            unsafe {
-                Win32_VariantClear(&mut device_id_property);
-                Win32_VariantClear(&mut free_space_property);
-                Win32_VariantClear(&mut size_property);
-            }
-        }
-
-        Ok(Self {
-            Drives: vector
-        })
-    }
-}
-
-impl fmt::Display for Storage {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for drive in &self.Drives {
-            write!(f, "{}", drive)?;
-        }
-
-        Ok(())
-    }
-}
-
-pub struct StorageDrive {
-    DriveLetter: OsString,
-    FreeSpace: f64,
-    Size: f64
-}
-
-impl fmt::Display for StorageDrive {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let used = self.Size - self.FreeSpace;
-        let total = self.Size;
-
-        write!(
-            f,
-            "{}34m Drive ({}){}0m: {} {} / {}",
-            utils::ANSI_ESCAPE_SEQUENCE,
-            self.DriveLetter.clone().into_string().unwrap(),
-            utils::ANSI_ESCAPE_SEQUENCE,
-            utils::GeneratePercentageBar(((used / total) * 100.0) as i32).unwrap(),
-            __internals::__InternalsToUnits(used),
-            __internals::__InternalsToUnits(total)
-        )
-    }
-}
+use std::{
+    collections::BTreeMap,
+    fmt
+};
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+use crate::winfetch::{
+    __internals::{
+        self,
+        wmi::{
+            self,
+            WmiRow
+        }
+    },
+    catalog,
+    error::WinfetchResult,
+    utils
+};
+
+// the values of `Win32_LogicalDisk.DriveType`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DriveType {
+    #[serde(rename = "unknown")]
+    Unknown,
+    #[serde(rename = "removable")]
+    Removable,
+    #[serde(rename = "fixed")]
+    Fixed,
+    #[serde(rename = "network")]
+    Network,
+    #[serde(rename = "optical")]
+    Optical,
+    #[serde(rename = "ram")]
+    Ram
+}
+
+impl DriveType {
+    pub fn FromValue(value: i64) -> Self {
+        match value {
+            2 => Self::Removable,
+            3 => Self::Fixed,
+            4 => Self::Network,
+            5 => Self::Optical,
+            6 => Self::Ram,
+            _ => Self::Unknown
+        }
+    }
+}
+
+impl fmt::Display for DriveType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", catalog::Message(match self {
+            Self::Unknown => "drive_unknown",
+            Self::Removable => "drive_removable",
+            Self::Fixed => "drive_fixed",
+            Self::Network => "drive_network",
+            Self::Optical => "drive_optical",
+            Self::Ram => "drive_ram"
+        }))
+    }
+}
+
+// the values of `MSFT_PhysicalDisk.MediaType`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum MediaType {
+    #[serde(rename = "hdd")]
+    Hdd,
+    #[serde(rename = "ssd")]
+    Ssd,
+    #[serde(rename = "scm")]
+    Scm
+}
+
+impl MediaType {
+    pub fn FromValue(value: i64) -> Option<Self> {
+        match value {
+            3 => Some(Self::Hdd),
+            4 => Some(Self::Ssd),
+            5 => Some(Self::Scm),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Hdd => "HDD",
+            Self::Ssd => "SSD",
+            Self::Scm => "SCM"
+        })
+    }
+}
+
+// the disk a drive is on; volumes spanning several disks show the first one
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PhysicalDisk {
+    pub Model: String,
+    pub MediaType: Option<MediaType>
+}
+
+impl fmt::Display for PhysicalDisk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.Model)?;
+
+        if let Some(media_type) = self.MediaType {
+            write!(f, " ({})", media_type)?;
+        }
+
+        Ok(())
+    }
+}
+
+// the drives to show; an empty list of drive types allows all of them
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StorageFilter {
+    pub DriveTypes: Vec<DriveType>,
+    /// The smallest drive shown, in bytes.
+    pub MinimumSize: u64
+}
+
+impl StorageFilter {
+    // drives without a size, like card readers without a card or disconnected network shares, are never shown
+    pub fn Allows(&self, drive: &StorageDrive) -> bool {
+        drive.Size > 0
+            && drive.Size >= self.MinimumSize
+            && (self.DriveTypes.is_empty() || self.DriveTypes.contains(&drive.DriveType))
+    }
+}
+
+#[derive(Serialize)]
+pub struct Storage {
+    pub Drives: Vec<StorageDrive>
+}
+
+impl Storage {
+    pub fn GetStorageStatistics(filter: &StorageFilter, physical_disks: bool) -> WinfetchResult<Self> {
+        let rows = wmi::__InternalsWmiQuery(
+            wmi::ROOT_CIMV2,
+            "SELECT DeviceID, DriveType, FileSystem, VolumeName, FreeSpace, Size FROM Win32_LogicalDisk",
+            &["DeviceID", "DriveType", "FileSystem", "VolumeName", "FreeSpace", "Size"]
+        )?;
+
+        let mut drives = rows
+            .iter()
+            .filter_map(StorageDrive::FromLogicalDisk)
+            .filter(|drive| filter.Allows(drive))
+            .collect::<Vec<_>>();
+
+        // the disk models are a nicety, so the drives are still shown when the storage classes cannot be queried
+        if physical_disks {
+            let disks = GetPhysicalDisks().unwrap_or_default();
+
+            for drive in &mut drives {
+                drive.Disk = disks.get(&drive.DriveLetter).cloned();
+            }
+        }
+
+        Ok(Self {
+            Drives: drives
+        })
+    }
+}
+
+// the physical disk of every drive letter, joined through the partitions
+fn GetPhysicalDisks() -> WinfetchResult<BTreeMap<String, PhysicalDisk>> {
+    let partitions = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT Antecedent, Dependent FROM Win32_LogicalDiskToPartition", &["Antecedent", "Dependent"])?;
+    let disk_drives = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT Index, Model FROM Win32_DiskDrive", &["Index", "Model"])?;
+
+    // `MSFT_PhysicalDisk` is missing before windows 8
+    let media_types = wmi::__InternalsWmiQuery(wmi::ROOT_STORAGE, "SELECT DeviceId, MediaType FROM MSFT_PhysicalDisk", &["DeviceId", "MediaType"])
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|row| Some((
+            row.get("DeviceId")?.AsInteger()?,
+            MediaType::FromValue(row.get("MediaType")?.AsInteger()?)?
+        )))
+        .collect::<BTreeMap<_, _>>();
+
+    let models = disk_drives
+        .iter()
+        .filter_map(|row| Some((row.get("Index")?.AsInteger()?, row.get("Model")?.AsString()?.trim().to_string())))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut disks = BTreeMap::new();
+
+    for row in &partitions {
+        let drive_letter = row.get("Dependent").and_then(|value| value.AsString()).and_then(ParseObjectPathKey);
+        let disk_index = row.get("Antecedent").and_then(|value| value.AsString()).and_then(ParseObjectPathKey).and_then(|key| ParseDiskIndex(&key));
+
+        if let (Some(drive_letter), Some(disk_index)) = (drive_letter, disk_index) {
+            if let Some(model) = models.get(&disk_index) {
+                disks.entry(drive_letter).or_insert_with(|| PhysicalDisk {
+                    Model: model.clone(),
+                    MediaType: media_types.get(&disk_index).copied()
+                });
+            }
+        }
+    }
+
+    Ok(disks)
+}
+
+// `\\HOST\root\cimv2:Win32_LogicalDisk.DeviceID="C:"` holds the key `C:`; backslashes in the key are escaped
+pub fn ParseObjectPathKey(path: &str) -> Option<String> {
+    let (_, key) = path.split_once("=\"")?;
+    let key = key.strip_suffix('"')?;
+
+    Some(key.replace("\\\\", "\\"))
+}
+
+// partitions are named `Disk #0, Partition #1`
+pub fn ParseDiskIndex(partition: &str) -> Option<i64> {
+    partition.strip_prefix("Disk #")?.split(',').next()?.trim().parse().ok()
+}
+
+impl fmt::Display for Storage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let drives = self.Drives.iter().map(|drive| format!("({}) {}", drive.DriveLetter, drive)).collect::<Vec<_>>();
+
+        write!(f, "{}", drives.join(", "))
+    }
+}
+
+#[derive(Serialize)]
+pub struct StorageDrive {
+    /// The drive letter with its colon, like `C:`.
+    pub DriveLetter: String,
+    pub DriveType: DriveType,
+    /// `NTFS`, `ReFS`, `FAT32` and so on; `None` for unformatted drives.
+    pub FileSystem: Option<String>,
+    /// The label of the volume; `None` when it has none.
+    pub VolumeName: Option<String>,
+    /// The space available on the drive, in bytes.
+    pub FreeSpace: u64,
+    /// The capacity of the drive, in bytes.
+    pub Size: u64,
+    /// `None` unless the physical disks were requested, or when the drive is not on a local disk.
+    pub Disk: Option<PhysicalDisk>
+}
+
+impl StorageDrive {
+    // drives without a medium, like empty optical drives, have no size and are left out
+    pub fn FromLogicalDisk(row: &WmiRow) -> Option<Self> {
+        let text = |property: &str| row
+            .get(property)
+            .and_then(|value| value.AsString())
+            .map(str::trim)
+            .filter(|string| !string.is_empty())
+            .map(String::from);
+
+        Some(Self {
+            DriveLetter: row.get("DeviceID")?.AsString()?.to_string(),
+            DriveType: DriveType::FromValue(row.get("DriveType").and_then(|value| value.AsInteger()).unwrap_or_default()),
+            FileSystem: text("FileSystem"),
+            VolumeName: text("VolumeName"),
+            FreeSpace: row.get("FreeSpace")?.AsInteger()? as u64,
+            Size: row.get("Size")?.AsInteger()? as u64,
+            Disk: None
+        })
+    }
+
+    pub fn Used(&self) -> u64 {
+        self.Size.saturating_sub(self.FreeSpace)
+    }
+}
+
+impl fmt::Display for StorageDrive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let used = self.Used() as f64;
+        let total = self.Size as f64;
+        let percentage = if self.Size == 0 { 0 } else { ((used / total) * 100.0) as i32 };
+
+        write!(
+            f,
+            "{} {} / {}",
+            utils::GeneratePercentageBar(percentage),
+            __internals::__InternalsToUnits(used),
+            __internals::__InternalsToUnits(total)
+        )?;
+
+        let details = [self.VolumeName.clone(), self.FileSystem.clone(), Some(self.DriveType.to_string())]
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        write!(f, " ({})", details.join(", "))?;
+
+        if let Some(disk) = &self.Disk {
+            write!(f, " {}", catalog::Format("on_disk", &[("disk", &disk.to_string())]))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::wmi,
+    catalog,
+    error::WinfetchResult,
+    utils
+};
+
+// the temperatures from which a zone is shown in yellow and red
+const WARM_THRESHOLD: f64 = 60.0;
+const HOT_THRESHOLD: f64 = 80.0;
+
+#[derive(Serialize)]
+pub struct ThermalZone {
+    /// The short name of the acpi thermal zone, like `TZ00`.
+    pub Name: String,
+    /// In degrees celsius.
+    pub Celsius: f64
+}
+
+impl fmt::Display for ThermalZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let color = if self.Celsius >= HOT_THRESHOLD {
+            "91m"
+        }
+        else if self.Celsius >= WARM_THRESHOLD {
+            "93m"
+        }
+        else {
+            "32m"
+        };
+
+        write!(f, "{} {}{}{:.1}°C{}0m", self.Name, utils::ANSI_ESCAPE_SEQUENCE, color, self.Celsius, utils::ANSI_ESCAPE_SEQUENCE)
+    }
+}
+
+#[derive(Serialize)]
+pub struct Temperature {
+    /// The acpi thermal zones; graphics cards are not included, as their temperature is only exposed by the vendor drivers.
+    pub Zones: Vec<ThermalZone>
+}
+
+impl Temperature {
+    pub fn GetTemperature() -> WinfetchResult<Self> {
+        let mut zones = Vec::new();
+
+        // `MSAcpi_ThermalZoneTemperature` usually requires administrator rights, so failing queries only leave the zones out
+        let acpi_rows = wmi::__InternalsWmiQuery(
+            wmi::ROOT_WMI,
+            "SELECT InstanceName, CurrentTemperature FROM MSAcpi_ThermalZoneTemperature",
+            &["InstanceName", "CurrentTemperature"]
+        )
+            .unwrap_or_default();
+
+        for row in &acpi_rows {
+            if let (Some(name), Some(temperature)) = (
+                row.get("InstanceName").and_then(|value| value.AsString()),
+                row.get("CurrentTemperature").and_then(|value| value.AsInteger())
+            ) {
+                zones.push(ThermalZone { Name: ZoneName(name), Celsius: TenthsOfKelvinToCelsius(temperature) });
+            }
+        }
+
+        let performance_rows = wmi::__InternalsWmiQuery(
+            wmi::ROOT_CIMV2,
+            "SELECT Name, HighPrecisionTemperature FROM Win32_PerfFormattedData_Counters_ThermalZoneInformation",
+            &["Name", "HighPrecisionTemperature"]
+        )
+            .unwrap_or_default();
+
+        for row in &performance_rows {
+            if let (Some(name), Some(temperature)) = (
+                row.get("Name").and_then(|value| value.AsString()),
+                row.get("HighPrecisionTemperature").and_then(|value| value.AsInteger())
+            ) {
+                let name = ZoneName(name);
+
+                // both classes usually describe the same acpi thermal zones
+                if !zones.iter().any(|zone| zone.Name == name) {
+                    zones.push(ThermalZone { Name: name, Celsius: TenthsOfKelvinToCelsius(temperature) });
+                }
+            }
+        }
+
+        Ok(Self { Zones: zones })
+    }
+
+    pub fn Maximum(&self) -> Option<&ThermalZone> {
+        self.Zones.iter().max_by(|a, b| a.Celsius.total_cmp(&b.Celsius))
+    }
+}
+
+pub fn TenthsOfKelvinToCelsius(temperature: i64) -> f64 {
+    temperature as f64 / 10.0 - 273.15
+}
+
+// turns `ACPI\ThermalZone\TZ00_0` and `\_TZ.TZ00` into `TZ00`
+pub fn ZoneName(instance_name: &str) -> String {
+    let name = instance_name.rsplit(['\\', '.']).next().unwrap_or(instance_name);
+
+    match name.rsplit_once('_') {
+        Some((name, index)) if !name.is_empty() && !index.is_empty() && index.chars().all(|char| char.is_ascii_digit()) => name.to_string(),
+        _ => name.to_string()
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.Zones.is_empty() {
+            return write!(f, "{}", catalog::Message("unavailable"));
+        }
+
+        write!(f, "{}", self.Zones.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))
+    }
+}
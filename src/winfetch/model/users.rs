@@ -0,0 +1,258 @@
+use std::fmt;
+
+#[cfg(windows)]
+use std::{
+    mem,
+    ptr,
+    slice
+};
+
+use chrono::{
+    DateTime,
+    Local,
+    TimeZone
+};
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::{
+        minwindef::{
+            BOOL,
+            DWORD
+        },
+        ntdef::{
+            HANDLE,
+            LPWSTR,
+            PVOID
+        }
+    },
+    um::errhandlingapi::GetLastError as Win32_GetLastError
+};
+
+use crate::winfetch::{
+    catalog,
+    dump::{
+        self,
+        RecordKey
+    },
+    error::WinfetchResult
+};
+
+#[cfg(windows)]
+use crate::winfetch::error::WinfetchError;
+
+// the seconds between 1601-01-01, the epoch of `FILETIME`, and 1970-01-01
+const FILETIME_UNIX_EPOCH_SECONDS: i64 = 11_644_473_600;
+
+// the values of `WTS_INFO_CLASS` and `WTS_CONNECTSTATE_CLASS`
+#[cfg(windows)]
+const WTS_SESSION_INFO: i32 = 24;
+#[cfg(windows)]
+const WTS_CLIENT_PROTOCOL_TYPE: i32 = 16;
+#[cfg(windows)]
+const WTS_ACTIVE: i32 = 0;
+#[cfg(windows)]
+const WTS_DISCONNECTED: i32 = 4;
+
+// `wtsapi32.h` is not covered by winapi beyond `WTSQueryUserToken`
+#[cfg(windows)]
+#[repr(C)]
+struct WtsSessionInfo {
+    SessionId: DWORD,
+    pWinStationName: LPWSTR,
+    State: i32
+}
+
+#[cfg(windows)]
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct WtsInfo {
+    State: i32,
+    SessionId: DWORD,
+    IncomingBytes: DWORD,
+    OutgoingBytes: DWORD,
+    IncomingFrames: DWORD,
+    OutgoingFrames: DWORD,
+    IncomingCompressedBytes: DWORD,
+    OutgoingCompressedBytes: DWORD,
+    WinStationName: [u16; 32],
+    Domain: [u16; 17],
+    UserName: [u16; 21],
+    ConnectTime: i64,
+    DisconnectTime: i64,
+    LastInputTime: i64,
+    LogonTime: i64,
+    CurrentTime: i64
+}
+
+#[cfg(windows)]
+#[link(name = "wtsapi32")]
+extern "system" {
+    fn WTSEnumerateSessionsW(hServer: HANDLE, Reserved: DWORD, Version: DWORD, ppSessionInfo: *mut *mut WtsSessionInfo, pCount: *mut DWORD) -> BOOL;
+    fn WTSQuerySessionInformationW(hServer: HANDLE, SessionId: DWORD, WTSInfoClass: i32, ppBuffer: *mut LPWSTR, pBytesReturned: *mut DWORD) -> BOOL;
+    fn WTSFreeMemory(pMemory: PVOID);
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum SessionKind {
+    #[serde(rename = "console")]
+    Console,
+    #[serde(rename = "rdp")]
+    Remote
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum SessionState {
+    #[serde(rename = "active")]
+    Active,
+    #[serde(rename = "disconnected")]
+    Disconnected,
+    // connected without a user logged on yet, or idle
+    #[serde(rename = "other")]
+    Other
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Session {
+    pub SessionId: u32,
+    pub UserName: String,
+    pub Domain: String,
+    pub Kind: SessionKind,
+    pub State: SessionState,
+    /// In local time; `None` when windows does not know it.
+    pub LogonTime: Option<DateTime<Local>>
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}", self.UserName, catalog::Message(match self.Kind {
+            SessionKind::Console => "console",
+            SessionKind::Remote => "rdp"
+        }))?;
+
+        if self.State == SessionState::Disconnected {
+            write!(f, ", {}", catalog::Message("disconnected"))?;
+        }
+
+        if let Some(logon_time) = self.LogonTime {
+            write!(f, ", {}", catalog::Format("since", &[("time", &logon_time.format("%Y-%m-%d %H:%M").to_string())]))?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+// `FILETIME` ticks are 100 nanosecond intervals since 1601; zero means the time is unknown
+pub fn FileTimeToDateTime(ticks: i64) -> Option<DateTime<Local>> {
+    if ticks <= 0 {
+        return None;
+    }
+
+    Local.timestamp_opt(ticks / 10_000_000 - FILETIME_UNIX_EPOCH_SECONDS, (ticks % 10_000_000 * 100) as u32).single()
+}
+
+#[derive(Serialize)]
+pub struct Users {
+    pub Sessions: Vec<Session>
+}
+
+impl Users {
+    pub fn GetUsers() -> WinfetchResult<Self> {
+        Ok(Self {
+            Sessions: dump::Intercept(RecordKey::Call("WTSEnumerateSessions"), EnumerateSessions)?
+        })
+    }
+
+    // the names of the users with a session, each listed once
+    pub fn UserNames(&self) -> Vec<String> {
+        let mut user_names = Vec::<String>::new();
+
+        for session in &self.Sessions {
+            if !user_names.iter().any(|user_name| user_name.eq_ignore_ascii_case(&session.UserName)) {
+                user_names.push(session.UserName.clone());
+            }
+        }
+
+        user_names
+    }
+}
+
+impl fmt::Display for Users {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.Sessions.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))
+    }
+}
+
+#[cfg(not(windows))]
+fn EnumerateSessions() -> WinfetchResult<Vec<Session>> {
+    Err(crate::winfetch::__internals::__InternalsUnsupportedPlatform("WTSEnumerateSessionsW"))
+}
+
+// the sessions without a user, like the services session and the rdp listener, are left out
+#[cfg(windows)]
+fn EnumerateSessions() -> WinfetchResult<Vec<Session>> {
+    let mut session_infos = ptr::null_mut();
+    let mut count = 0;
+
+    // a null handle is `WTS_CURRENT_SERVER_HANDLE`
+    if unsafe { WTSEnumerateSessionsW(ptr::null_mut(), 0, 1, &mut session_infos, &mut count) } == 0 {
+        return Err(WinfetchError(format!("could not enumerate sessions; error code: {}", unsafe { Win32_GetLastError() })));
+    }
+
+    let sessions = unsafe { slice::from_raw_parts(session_infos, count as usize) }
+        .iter()
+        .filter_map(|session_info| unsafe { ReadSession(session_info.SessionId) })
+        .collect();
+
+    unsafe { WTSFreeMemory(session_infos as PVOID) };
+
+    Ok(sessions)
+}
+
+#[cfg(windows)]
+unsafe fn QuerySessionInformation<T: Copy>(session_id: DWORD, info_class: i32) -> Option<T> {
+    let mut buffer = ptr::null_mut();
+    let mut length = 0;
+
+    if WTSQuerySessionInformationW(ptr::null_mut(), session_id, info_class, &mut buffer, &mut length) == 0 || buffer.is_null() {
+        return None;
+    }
+
+    let value = (length as usize >= mem::size_of::<T>()).then(|| ptr::read_unaligned(buffer as *const T));
+    WTSFreeMemory(buffer as PVOID);
+
+    value
+}
+
+#[cfg(windows)]
+unsafe fn ReadSession(session_id: DWORD) -> Option<Session> {
+    let wide = |wide: &[u16]| String::from_utf16_lossy(&wide[..wide.iter().position(|&char| char == 0).unwrap_or(wide.len())]);
+
+    let info = QuerySessionInformation::<WtsInfo>(session_id, WTS_SESSION_INFO)?;
+    let user_name = wide(&info.UserName);
+
+    if user_name.is_empty() {
+        return None;
+    }
+
+    // 0 is the console, 2 is rdp
+    let protocol_type = QuerySessionInformation::<u16>(session_id, WTS_CLIENT_PROTOCOL_TYPE).unwrap_or_default();
+
+    Some(Session {
+        SessionId: session_id,
+        UserName: user_name,
+        Domain: wide(&info.Domain),
+        Kind: if protocol_type == 0 { SessionKind::Console } else { SessionKind::Remote },
+        State: match info.State {
+            WTS_ACTIVE => SessionState::Active,
+            WTS_DISCONNECTED => SessionState::Disconnected,
+            _ => SessionState::Other
+        },
+        LogonTime: FileTimeToDateTime(info.LogonTime)
+    })
+}
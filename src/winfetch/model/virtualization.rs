@@ -0,0 +1,190 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::{
+        registry::{
+            self,
+            RegistryHive
+        },
+        wmi
+    },
+    catalog,
+    error::{
+        WinfetchError,
+        WinfetchResult
+    }
+};
+
+// clouds are ordered before the hypervisors they run on, so that they win when both are detected
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Hypervisor {
+    Azure,
+    AWS,
+    GCE,
+    VMware,
+    VirtualBox,
+    Parallels,
+    Xen,
+    QEMU,
+    HyperV
+}
+
+impl fmt::Display for Hypervisor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Azure => "Microsoft Azure",
+            Self::AWS => "Amazon EC2",
+            Self::GCE => "Google Compute Engine",
+            Self::VMware => "VMware",
+            Self::VirtualBox => "VirtualBox",
+            Self::Parallels => "Parallels",
+            Self::Xen => "Xen",
+            Self::QEMU => "QEMU/KVM",
+            Self::HyperV => "Hyper-V"
+        })
+    }
+}
+
+// how a signature is compared with a whole smbios string, ignoring the case and the surrounding whitespace
+#[derive(Clone, Copy, Debug)]
+pub enum Signature {
+    Exact(&'static str),
+    // for the strings that carry a version after the vendor, such as `VMware7,1` or `BOCHS  - 1`
+    Prefix(&'static str)
+}
+
+impl Signature {
+    pub fn Matches(&self, string: &str) -> bool {
+        let string = string.trim().to_lowercase();
+
+        match *self {
+            Self::Exact(signature) => string == signature,
+            Self::Prefix(signature) => string.starts_with(signature)
+        }
+    }
+}
+
+// the smbios manufacturer, model, bios and asset tag strings set by each hypervisor
+pub const SMBIOS_SIGNATURES: &[(Signature, Hypervisor)] = &[
+    // the chassis asset tag set on every azure virtual machine
+    (Signature::Exact("7783-7084-3265-9085-8269-3286-77"), Hypervisor::Azure),
+    (Signature::Exact("amazon ec2"), Hypervisor::AWS),
+    (Signature::Exact("google compute engine"), Hypervisor::GCE),
+    (Signature::Prefix("vmware"), Hypervisor::VMware),
+    (Signature::Exact("virtualbox"), Hypervisor::VirtualBox),
+    (Signature::Exact("innotek gmbh"), Hypervisor::VirtualBox),
+    (Signature::Prefix("parallels"), Hypervisor::Parallels),
+    // `Xen` as the manufacturer, `HVM domU` as the model
+    (Signature::Exact("xen"), Hypervisor::Xen),
+    (Signature::Exact("hvm domu"), Hypervisor::Xen),
+    (Signature::Exact("qemu"), Hypervisor::QEMU),
+    (Signature::Exact("kvm"), Hypervisor::QEMU),
+    (Signature::Prefix("bochs "), Hypervisor::QEMU),
+    (Signature::Exact("virtual machine"), Hypervisor::HyperV),
+    (Signature::Prefix("vrtual "), Hypervisor::HyperV)
+];
+
+// keys under `HKEY_LOCAL_MACHINE` created by the guest tools and agents
+pub const REGISTRY_MARKERS: &[(&str, Hypervisor)] = &[
+    (r"SOFTWARE\Microsoft\Windows Azure", Hypervisor::Azure),
+    (r"SOFTWARE\Amazon\EC2ConfigService", Hypervisor::AWS),
+    (r"SOFTWARE\Amazon\EC2Launch", Hypervisor::AWS),
+    (r"SOFTWARE\Google\ComputeEngine", Hypervisor::GCE),
+    (r"SOFTWARE\VMware, Inc.\VMware Tools", Hypervisor::VMware),
+    (r"SOFTWARE\Oracle\VirtualBox Guest Additions", Hypervisor::VirtualBox),
+    (r"SOFTWARE\Parallels\Parallels Tools", Hypervisor::Parallels),
+    (r"SOFTWARE\Citrix\XenTools", Hypervisor::Xen),
+    (r"SYSTEM\CurrentControlSet\Services\QEMU-GA", Hypervisor::QEMU),
+    (r"SOFTWARE\Microsoft\Virtual Machine\Guest\Parameters", Hypervisor::HyperV)
+];
+
+pub fn ClassifyHypervisor(smbios_strings: &[&str], registry_markers: &[&str]) -> Option<Hypervisor> {
+    let from_smbios = smbios_strings.iter().flat_map(|string| {
+        SMBIOS_SIGNATURES
+            .iter()
+            .filter(move |(signature, _)| signature.Matches(string))
+            .map(|&(_, hypervisor)| hypervisor)
+    });
+    let from_registry = REGISTRY_MARKERS
+        .iter()
+        .filter(|(path, _)| registry_markers.iter().any(|marker| marker.eq_ignore_ascii_case(path)))
+        .map(|&(_, hypervisor)| hypervisor);
+
+    from_smbios.chain(from_registry).min()
+}
+
+// `Win32_DeviceGuard.VirtualizationBasedSecurityStatus`; 1 means enabled, but not running
+const VBS_RUNNING: i64 = 2;
+
+#[derive(Serialize)]
+pub struct Virtualization {
+    pub Hypervisor: Option<Hypervisor>,
+    pub HypervisorPresent: bool,
+    /// Whether virtualization-based security is running, not only enabled by policy.
+    pub VirtualizationBasedSecurity: bool
+}
+
+impl Virtualization {
+    pub fn GetVirtualization() -> WinfetchResult<Self> {
+        let computer_systems = wmi::__InternalsWmiQuery(
+            wmi::ROOT_CIMV2,
+            "SELECT Manufacturer, Model, HypervisorPresent FROM Win32_ComputerSystem",
+            &["Manufacturer", "Model", "HypervisorPresent"]
+        )?;
+        let computer_system = computer_systems.first().ok_or_else(|| WinfetchError(String::from("`Win32_ComputerSystem` returned no rows")))?;
+        let bioses = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT Manufacturer, Version FROM Win32_BIOS", &["Manufacturer", "Version"])?;
+        let enclosures = wmi::__InternalsWmiQuery(wmi::ROOT_CIMV2, "SELECT SMBIOSAssetTag FROM Win32_SystemEnclosure", &["SMBIOSAssetTag"])?;
+
+        let smbios_strings = computer_systems
+            .iter()
+            .chain(&bioses)
+            .chain(&enclosures)
+            .flat_map(|row| row.values())
+            .filter_map(|value| value.AsString())
+            .collect::<Vec<_>>();
+
+        // a key that cannot be opened is treated as missing
+        let registry_markers = REGISTRY_MARKERS
+            .iter()
+            .map(|&(path, _)| path)
+            .filter(|path| registry::__InternalsRegistryKeyExists(RegistryHive::LocalMachine, path).unwrap_or(false))
+            .collect::<Vec<_>>();
+
+        // the class is missing before windows 10, where vbs cannot run anyway
+        let virtualization_based_security = wmi::__InternalsWmiQuery(
+            wmi::ROOT_DEVICE_GUARD,
+            "SELECT VirtualizationBasedSecurityStatus FROM Win32_DeviceGuard",
+            &["VirtualizationBasedSecurityStatus"]
+        )
+            .is_ok_and(|rows| {
+                rows.iter()
+                    .filter_map(|row| row.get("VirtualizationBasedSecurityStatus").and_then(|value| value.AsInteger()))
+                    .any(|status| status == VBS_RUNNING)
+            });
+
+        Ok(Self {
+            Hypervisor: ClassifyHypervisor(&smbios_strings, &registry_markers),
+            HypervisorPresent: wmi::__InternalsWmiGet(computer_system, "HypervisorPresent")?.AsBoolean().unwrap_or(false),
+            VirtualizationBasedSecurity: virtualization_based_security
+        })
+    }
+}
+
+impl fmt::Display for Virtualization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.Hypervisor {
+            Some(hypervisor) => write!(f, "{}", catalog::Format("guest", &[("hypervisor", &hypervisor.to_string())]))?,
+            // on bare metal, a present hypervisor means that hyper-v is enabled on the host
+            None if self.HypervisorPresent => write!(f, "{}, {}", catalog::Message("bare_metal"), catalog::Message("hyper_v_enabled"))?,
+            None => write!(f, "{}", catalog::Message("bare_metal"))?
+        }
+
+        if self.VirtualizationBasedSecurity {
+            write!(f, ", {}", catalog::Message("vbs_on"))?;
+        }
+
+        Ok(())
+    }
+}
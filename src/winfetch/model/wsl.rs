@@ -0,0 +1,91 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    __internals::registry::{
+        self,
+        RegistryHive,
+        RegistryKey
+    },
+    catalog,
+    error::WinfetchResult,
+    utils
+};
+
+const LXSS_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Lxss";
+
+// `LXSS_DISTRO_FLAGS_VM_MODE`, set on the distributions running in the wsl 2 virtual machine
+const VM_MODE_FLAG: u32 = 0x8;
+
+#[derive(Serialize)]
+pub struct WslDistribution {
+    /// The directory holding the disk image, as a `/mnt/<drive>` path.
+    pub BasePath: String,
+    /// Whether `wsl` starts this distribution when none is named.
+    pub Default: bool,
+    pub DistributionName: String,
+    /// 1 or 2.
+    pub Version: u32
+}
+
+impl fmt::Display for WslDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WSL {}", self.Version)?;
+
+        if self.Default {
+            write!(f, ", {}", catalog::Message("default"))?;
+        }
+
+        write!(f, ", {}", self.BasePath)
+    }
+}
+
+#[derive(Serialize)]
+pub struct Wsl {
+    pub Distributions: Vec<WslDistribution>
+}
+
+impl Wsl {
+    pub fn GetWsl() -> WinfetchResult<Self> {
+        Ok(match registry::__InternalsRegistryReadKey(RegistryHive::CurrentUser, LXSS_KEY)? {
+            Some(lxss_key) => Self::FromLxssKey(&lxss_key),
+            None => Self { Distributions: Vec::new() }
+        })
+    }
+
+    // every distribution is a subkey named after its guid; the default one is referenced by `DefaultDistribution`
+    pub fn FromLxssKey(lxss_key: &RegistryKey) -> Self {
+        let default_distribution = lxss_key.Values.get("DefaultDistribution").and_then(|value| value.AsString());
+
+        let distributions = lxss_key.SubKeys
+            .iter()
+            .filter_map(|(guid, key)| {
+                let distribution_name = key.Values.get("DistributionName")?.AsString()?;
+
+                Some(WslDistribution {
+                    BasePath: utils::ToMntPath(key.Values.get("BasePath").and_then(|value| value.AsString()).unwrap_or_default()),
+                    Default: default_distribution.is_some_and(|default| default.eq_ignore_ascii_case(guid)),
+                    DistributionName: distribution_name.to_string(),
+                    // `Version` is the format of the registration, not the wsl version of the distribution
+                    Version: match key.Values.get("Flags").and_then(|value| value.AsDword()) {
+                        Some(flags) if flags & VM_MODE_FLAG != 0 => 2,
+                        _ => 1
+                    }
+                })
+            })
+            .collect();
+
+        Self { Distributions: distributions }
+    }
+}
+
+impl fmt::Display for Wsl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.Distributions
+            .iter()
+            .map(|distribution| format!("({}) {}", distribution.DistributionName, distribution))
+            .collect::<Vec<_>>()
+            .join(", "))
+    }
+}
@@ -0,0 +1,572 @@
+use std::collections::BTreeMap;
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+use crate::winfetch::{
+    catalog,
+    error::WinfetchError,
+    model::{
+        locale::TimeZone,
+        names::{
+            Membership,
+            NameCase
+        }
+    },
+    report::SystemReport,
+    template::{
+        PlaceholderKind::{
+            self,
+            Bytes,
+            DateTime,
+            Integer,
+            Percent,
+            Text
+        },
+        Template,
+        TemplateValue
+    },
+    utils
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Module {
+    #[serde(rename = "title")]
+    Title,
+    #[serde(rename = "underline")]
+    Underline,
+    #[serde(rename = "os")]
+    OS,
+    #[serde(rename = "edition")]
+    Edition,
+    #[serde(rename = "install_date")]
+    InstallDate,
+    #[serde(rename = "activation")]
+    Activation,
+    #[serde(rename = "host")]
+    HostSystem,
+    #[serde(rename = "kernel")]
+    Kernel,
+    #[serde(rename = "motherboard")]
+    Motherboard,
+    #[serde(rename = "bios")]
+    Bios,
+    #[serde(rename = "virtualization")]
+    Virtualization,
+    #[serde(rename = "uptime")]
+    Uptime,
+    #[serde(rename = "resolution")]
+    ScreenResolution,
+    #[serde(rename = "cpu")]
+    Processor,
+    #[serde(rename = "gpu")]
+    GraphicsCard,
+    #[serde(rename = "cpu_usage")]
+    ProcessorUsage,
+    #[serde(rename = "cpu_cores")]
+    ProcessorCores,
+    #[serde(rename = "memory")]
+    Memory,
+    #[serde(rename = "temperature")]
+    Temperature,
+    #[serde(rename = "battery")]
+    Battery,
+    #[serde(rename = "storage")]
+    Storage,
+    #[serde(rename = "wsl")]
+    Wsl,
+    #[serde(rename = "locale")]
+    Locale,
+    #[serde(rename = "users")]
+    Users,
+    #[serde(rename = "top")]
+    Top,
+    #[serde(rename = "custom")]
+    Custom
+}
+
+impl Module {
+    pub fn DefaultModules() -> Vec<Self> {
+        vec![
+            Self::Title,
+            Self::Underline,
+            Self::OS,
+            Self::HostSystem,
+            Self::Kernel,
+            Self::Motherboard,
+            Self::Bios,
+            Self::Virtualization,
+            Self::Uptime,
+            Self::ScreenResolution,
+            Self::Processor,
+            Self::GraphicsCard,
+            Self::ProcessorUsage,
+            Self::Memory,
+            Self::Storage,
+            Self::Wsl,
+            Self::Custom
+        ]
+    }
+
+    // the name of the module in the config file
+    pub fn Name(&self) -> String {
+        serde_json::to_value(self).ok().and_then(|value| value.as_str().map(String::from)).unwrap_or_default()
+    }
+
+    pub fn FromName(name: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+    }
+
+    // the modules whose values change while the system is running
+    pub fn IsDynamic(&self) -> bool {
+        matches!(self, Self::Uptime | Self::ProcessorUsage | Self::ProcessorCores | Self::Memory | Self::Temperature | Self::Battery | Self::Storage | Self::Users | Self::Top)
+    }
+
+    pub fn TemplatePlaceholders(&self) -> &'static [(&'static str, PlaceholderKind)] {
+        match self {
+            Self::Title => &[
+                ("user", Text),
+                ("host", Text),
+                ("domain_user", Text),
+                ("upn", Text),
+                ("fqdn", Text),
+                ("domain", Text),
+                ("workgroup", Text)
+            ],
+            Self::Underline => &[],
+            Self::OS => &[("product", Text), ("version", Text), ("build", Text), ("arch", Text), ("edition", Text)],
+            Self::Edition => &[("edition", Text)],
+            Self::InstallDate => &[("date", DateTime)],
+            Self::Activation => &[("status", Text)],
+            Self::HostSystem => &[("manufacturer", Text), ("model", Text)],
+            Self::Kernel => &[("major", Integer), ("minor", Integer), ("build", Text), ("ubr", Integer)],
+            Self::Motherboard => &[("manufacturer", Text), ("product", Text)],
+            Self::Bios => &[("manufacturer", Text), ("version", Text), ("date", DateTime), ("boot_mode", Text), ("secure_boot", Text)],
+            Self::Virtualization => &[("hypervisor", Text), ("vbs", Text)],
+            Self::Uptime => &[("days", Integer), ("hours", Integer), ("minutes", Integer), ("boot", DateTime)],
+            Self::ScreenResolution => &[("resolutions", Text), ("names", Text), ("primary", Text), ("count", Integer)],
+            Self::Processor => &[("name", Text)],
+            Self::GraphicsCard => &[("names", Text)],
+            Self::ProcessorUsage => &[("percent", Percent), ("sockets", Text), ("processes", Integer)],
+            Self::ProcessorCores => &[("bars", Text), ("count", Integer)],
+            Self::Memory => &[("used", Bytes), ("free", Bytes), ("total", Bytes), ("percent", Percent)],
+            Self::Temperature => &[("zones", Text), ("max", Text)],
+            Self::Battery => &[("name", Text), ("percent", Percent), ("state", Text), ("minutes", Integer)],
+            Self::Storage => &[
+                ("drive", Text),
+                ("label", Text),
+                ("fs", Text),
+                ("type", Text),
+                ("disk", Text),
+                ("media", Text),
+                ("used", Bytes),
+                ("free", Bytes),
+                ("total", Bytes),
+                ("percent", Percent)
+            ],
+            Self::Wsl => &[("name", Text), ("version", Integer), ("default", Text), ("path", Text)],
+            Self::Locale => &[
+                ("language", Text),
+                ("format", Text),
+                ("date_format", Text),
+                ("timezone", Text),
+                ("offset", Text),
+                ("dst", Text),
+                ("keyboards", Text)
+            ],
+            Self::Users => &[("sessions", Text), ("users", Text), ("count", Integer)],
+            Self::Top => &[("processes", Text), ("count", Integer), ("threads", Integer)],
+            Self::Custom => &[("label", Text), ("value", Text)]
+        }
+    }
+
+    // the values of the placeholders, for every line the module renders
+    pub fn GetTemplateValues(&self, report: &SystemReport, name_case: NameCase) -> Vec<BTreeMap<&'static str, TemplateValue>> {
+        let text = |string: &str| TemplateValue::Text(string.to_string());
+        let on_off = |enabled: bool| TemplateValue::Text(String::from(if enabled { "on" } else { "off" }));
+        let percent = |part: u64, total: u64| TemplateValue::Percent(if total == 0 { 0.0 } else { part as f64 / total as f64 * 100.0 });
+
+        let values = match self {
+            Self::Title | Self::Underline => report.Names
+                .iter()
+                .map(|names| {
+                    let name = |name: &str| TemplateValue::Text(name_case.Apply(name));
+
+                    vec![
+                        ("user", name(&names.UserName)),
+                        ("host", name(&names.ComputerName)),
+                        ("domain_user", name(names.SamCompatibleName.as_ref().unwrap_or(&names.UserName))),
+                        ("upn", name(names.UserPrincipalName.as_deref().unwrap_or_default())),
+                        ("fqdn", name(names.FullyQualifiedName.as_ref().unwrap_or(&names.ComputerName))),
+                        ("domain", name(match &names.Membership {
+                            Some(Membership::Domain(domain)) => domain,
+                            _ => ""
+                        })),
+                        ("workgroup", name(match &names.Membership {
+                            Some(Membership::Workgroup(workgroup)) => workgroup,
+                            _ => ""
+                        }))
+                    ]
+                })
+                .collect(),
+            Self::OS | Self::Edition | Self::InstallDate | Self::Activation => report.OS
+                .iter()
+                .map(|os| vec![
+                    ("product", text(&os.ProductName)),
+                    ("version", text(&os.DisplayVersion)),
+                    ("build", text(&os.CurrentBuild)),
+                    ("arch", text(&os.OSArchitecture)),
+                    ("edition", text(&os.EditionID)),
+                    ("date", TemplateValue::DateTime(os.InstallDate.naive_local())),
+                    ("status", text(&os.ActivationStatus.map(|status| status.to_string()).unwrap_or_default()))
+                ])
+                .collect(),
+            Self::HostSystem => report.HostSystem
+                .iter()
+                .map(|host_system| vec![("manufacturer", text(&host_system.Manufacturer)), ("model", text(&host_system.Model))])
+                .collect(),
+            Self::Kernel => report.Kernel
+                .iter()
+                .map(|kernel| vec![
+                    ("major", TemplateValue::Integer(kernel.CurrentMajorVersionNumber as u64)),
+                    ("minor", TemplateValue::Integer(kernel.CurrentMinorVersionNumber as u64)),
+                    ("build", text(&kernel.CurrentBuild)),
+                    ("ubr", TemplateValue::Integer(kernel.UBR as u64))
+                ])
+                .collect(),
+            Self::Motherboard => report.Motherboard
+                .iter()
+                .map(|motherboard| vec![("manufacturer", text(&motherboard.Manufacturer)), ("product", text(&motherboard.Product))])
+                .collect(),
+            Self::Bios => report.Bios
+                .iter()
+                .map(|bios| {
+                    let mut values = vec![
+                        ("manufacturer", text(&bios.Manufacturer)),
+                        ("version", text(&bios.SMBIOSBIOSVersion)),
+                        ("boot_mode", text(&bios.BootMode.to_string())),
+                        ("secure_boot", bios.SecureBoot.map_or_else(|| text(""), on_off))
+                    ];
+                    values.extend(bios.ReleaseDate.and_then(|date| date.and_hms_opt(0, 0, 0)).map(|date| ("date", TemplateValue::DateTime(date))));
+
+                    values
+                })
+                .collect(),
+            Self::Virtualization => report.Virtualization
+                .iter()
+                .map(|virtualization| vec![
+                    ("hypervisor", text(&virtualization.Hypervisor.map_or_else(|| String::from("none"), |hypervisor| hypervisor.to_string()))),
+                    ("vbs", on_off(virtualization.VirtualizationBasedSecurity))
+                ])
+                .collect(),
+            Self::Uptime => report.Uptime
+                .iter()
+                .map(|uptime| {
+                    let minutes = uptime.Uptime.as_secs() / 60;
+
+                    vec![
+                        ("days", TemplateValue::Integer(minutes / (24 * 60))),
+                        ("hours", TemplateValue::Integer(minutes / 60 % 24)),
+                        ("minutes", TemplateValue::Integer(minutes % 60)),
+                        ("boot", TemplateValue::DateTime(uptime.LastBootUpTime.naive_local()))
+                    ]
+                })
+                .collect(),
+            Self::ScreenResolution => report.ScreenResolution
+                .iter()
+                .map(|screen_resolution| vec![
+                    ("resolutions", text(&screen_resolution.to_string())),
+                    ("names", text(&screen_resolution.Monitors.iter().filter_map(|monitor| monitor.Name.clone()).collect::<Vec<_>>().join(", "))),
+                    ("primary", text(&screen_resolution.Primary().map(|monitor| monitor.Mode()).unwrap_or_default())),
+                    ("count", TemplateValue::Integer(screen_resolution.Monitors.len() as u64))
+                ])
+                .collect(),
+            Self::Processor => report.Processor
+                .iter()
+                .map(|processor| vec![("name", text(&processor.Name))])
+                .collect(),
+            Self::GraphicsCard => report.GraphicsCard
+                .iter()
+                .map(|graphics_card| vec![("names", text(&graphics_card.Names.join(", ")))])
+                .collect(),
+            Self::ProcessorUsage => report.ProcessorUsage
+                .iter()
+                .map(|processor_usage| vec![
+                    ("percent", TemplateValue::Percent(processor_usage.LoadPercentage)),
+                    ("sockets", text(&processor_usage.Sockets
+                        .iter()
+                        .map(|socket| format!("{}: {:.0}%", socket.Name, socket.LoadPercentage))
+                        .collect::<Vec<_>>()
+                        .join(", "))),
+                    ("processes", TemplateValue::Integer(processor_usage.Processes))
+                ])
+                .collect(),
+            Self::ProcessorCores => report.ProcessorUsage
+                .iter()
+                .map(|processor_usage| vec![
+                    ("bars", text(&utils::StripANSIEscapeSequences(&processor_usage.CoreBars()))),
+                    ("count", TemplateValue::Integer(processor_usage.Cores.len() as u64))
+                ])
+                .collect(),
+            Self::Memory => report.Memory
+                .iter()
+                .map(|memory| vec![
+                    ("used", TemplateValue::Bytes(memory.Used())),
+                    ("free", TemplateValue::Bytes(memory.FreePhysicalMemory)),
+                    ("total", TemplateValue::Bytes(memory.TotalVisibleMemorySize)),
+                    ("percent", percent(memory.Used(), memory.TotalVisibleMemorySize))
+                ])
+                .collect(),
+            Self::Temperature => report.Temperature
+                .iter()
+                .map(|temperature| vec![
+                    ("zones", text(&utils::StripANSIEscapeSequences(&temperature.to_string()))),
+                    ("max", text(&temperature.Maximum().map(|zone| format!("{:.1}°C", zone.Celsius)).unwrap_or_default()))
+                ])
+                .collect(),
+            Self::Battery => report.Battery
+                .iter()
+                .flat_map(|battery| &battery.Batteries)
+                .map(|battery| {
+                    let mut values = vec![
+                        ("name", text(&battery.Name)),
+                        ("percent", TemplateValue::Percent(battery.ChargePercentage as f64)),
+                        ("state", text(&battery.State.to_string()))
+                    ];
+                    values.extend(battery.RemainingTime.map(|remaining_time| ("minutes", TemplateValue::Integer(remaining_time.as_secs() / 60))));
+
+                    values
+                })
+                .collect(),
+            Self::Storage => report.Storage
+                .iter()
+                .flat_map(|storage| &storage.Drives)
+                .map(|drive| vec![
+                    ("drive", text(&drive.DriveLetter)),
+                    ("label", text(drive.VolumeName.as_deref().unwrap_or_default())),
+                    ("fs", text(drive.FileSystem.as_deref().unwrap_or_default())),
+                    ("type", text(&drive.DriveType.to_string())),
+                    ("disk", text(drive.Disk.as_ref().map(|disk| disk.Model.as_str()).unwrap_or_default())),
+                    ("media", text(&drive.Disk.as_ref().and_then(|disk| disk.MediaType).map(|media_type| media_type.to_string()).unwrap_or_default())),
+                    ("used", TemplateValue::Bytes(drive.Used())),
+                    ("free", TemplateValue::Bytes(drive.FreeSpace)),
+                    ("total", TemplateValue::Bytes(drive.Size)),
+                    ("percent", percent(drive.Used(), drive.Size))
+                ])
+                .collect(),
+            Self::Wsl => report.Wsl
+                .iter()
+                .flat_map(|wsl| &wsl.Distributions)
+                .map(|distribution| vec![
+                    ("name", text(&distribution.DistributionName)),
+                    ("version", TemplateValue::Integer(distribution.Version as u64)),
+                    ("default", text(if distribution.Default { "default" } else { "" })),
+                    ("path", text(&distribution.BasePath))
+                ])
+                .collect(),
+            Self::Locale => report.Locale
+                .iter()
+                .map(|locale| vec![
+                    ("language", text(locale.DisplayLanguage.as_deref().unwrap_or_default())),
+                    ("format", text(locale.RegionalFormat.as_deref().unwrap_or_default())),
+                    ("date_format", text(locale.ShortDateFormat.as_deref().unwrap_or_default())),
+                    ("timezone", text(locale.TimeZone.as_ref().map(|time_zone| time_zone.Name.as_str()).unwrap_or_default())),
+                    ("offset", text(&locale.TimeZone.as_ref().map(TimeZone::UtcOffset).unwrap_or_default())),
+                    ("dst", on_off(locale.TimeZone.as_ref().is_some_and(|time_zone| time_zone.DaylightSaving))),
+                    ("keyboards", text(&locale.KeyboardLayouts.join(", ")))
+                ])
+                .collect(),
+            Self::Users => report.Users
+                .iter()
+                .map(|users| vec![
+                    ("sessions", text(&users.to_string())),
+                    ("users", text(&users.UserNames().join(", "))),
+                    ("count", TemplateValue::Integer(users.Sessions.len() as u64))
+                ])
+                .collect(),
+            Self::Top => report.Processes
+                .iter()
+                .map(|processes| vec![
+                    ("processes", text(&processes.Top.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))),
+                    ("count", TemplateValue::Integer(processes.Count)),
+                    ("threads", TemplateValue::Integer(processes.Threads))
+                ])
+                .collect(),
+            Self::Custom => report.Custom
+                .iter()
+                .flatten()
+                .map(|custom_value| vec![("label", text(&custom_value.Label)), ("value", text(&custom_value.to_string()))])
+                .collect::<Vec<_>>()
+        };
+
+        values.into_iter().map(|line_values| line_values.into_iter().collect()).collect()
+    }
+
+    // renders the parts of the report belonging to the module; nothing is rendered if they were not collected
+    pub fn GetInfoLines(&self, report: &SystemReport, template: Option<&Template>, name_case: NameCase) -> Vec<InfoLine> {
+        let lines = self.GetDefaultInfoLines(report, name_case);
+
+        match template {
+            Some(template) => lines
+                .into_iter()
+                .zip(self.GetTemplateValues(report, name_case))
+                .map(|(line, values)| line.WithValue(match template.Render(&values) {
+                    Ok(value) => value,
+                    // validation rejects the formats that cannot be rendered, so this is only a safety net
+                    Err(WinfetchError(error)) => format!("{}91m{}{}0m", utils::ANSI_ESCAPE_SEQUENCE, error, utils::ANSI_ESCAPE_SEQUENCE)
+                }))
+                .collect(),
+            None => lines
+        }
+    }
+
+    // the values of the modules joining several values on one line
+    fn GetSplitValues(&self, report: &SystemReport) -> Option<Vec<String>> {
+        match self {
+            Self::ScreenResolution => report.ScreenResolution
+                .as_ref()
+                .map(|screen_resolution| screen_resolution.Monitors.iter().map(ToString::to_string).collect()),
+            Self::GraphicsCard => report.GraphicsCard.as_ref().map(|graphics_card| graphics_card.Names.clone()),
+            Self::Temperature => report.Temperature.as_ref().map(|temperature| temperature.Zones.iter().map(ToString::to_string).collect()),
+            Self::Users => report.Users.as_ref().map(|users| users.Sessions.iter().map(ToString::to_string).collect()),
+            _ => None
+        }
+    }
+
+    // renders the lines of every module; the underline follows the width of the title before it, which templates can change,
+    // and with `split_values` the modules joining several values print one per line instead
+    pub fn GetModulesInfoLines(
+        modules: &[Self],
+        report: &SystemReport,
+        templates: &BTreeMap<Self, Template>,
+        name_case: NameCase,
+        split_values: bool
+    ) -> Vec<InfoLine> {
+        let mut lines = Vec::new();
+        let mut title_width = None;
+
+        for module in modules {
+            let mut module_lines = module.GetInfoLines(report, templates.get(module), name_case);
+
+            match module {
+                Self::Title => title_width = module_lines.last().map(|line| utils::VisibleWidth(line.Value())),
+                Self::Underline if !templates.contains_key(module) => if let Some(width) = title_width {
+                    module_lines = module_lines.into_iter().map(|line| line.WithValue("-".repeat(width))).collect();
+                },
+                _ if split_values && !templates.contains_key(module) => {
+                    if let (Some(values), Some(line)) = (module.GetSplitValues(report).filter(|values| values.len() > 1), module_lines.pop()) {
+                        let indent = " ".repeat(line.Label().map_or(0, |label| utils::VisibleWidth(&label) + 2));
+                        let mut values = values.into_iter();
+
+                        module_lines.push(line.WithValue(values.next().unwrap_or_default()));
+                        module_lines.extend(values.map(|value| InfoLine::Unlabeled(format!("{}{}", indent, value))));
+                    }
+                },
+                _ => ()
+            }
+
+            lines.extend(module_lines);
+        }
+
+        lines
+    }
+
+    fn GetDefaultInfoLines(&self, report: &SystemReport, name_case: NameCase) -> Vec<InfoLine> {
+        let labeled = |label: String, value: Option<String>| value.map(|value| InfoLine::Labeled(label, value)).into_iter().collect();
+
+        match self {
+            Self::Title => report.Names.iter().map(|names| InfoLine::Unlabeled(names.Title(name_case))).collect(),
+            Self::Underline => report.Names
+                .iter()
+                .map(|names| InfoLine::Unlabeled("-".repeat(names.UserName.chars().count() + names.ComputerName.chars().count() + 1)))
+                .collect(),
+            Self::OS => labeled(catalog::Message("os"), report.OS.as_ref().map(ToString::to_string)),
+            Self::Edition => labeled(catalog::Message("edition"), report.OS.as_ref().map(|os| os.EditionID.clone())),
+            Self::InstallDate => labeled(catalog::Message("install_date"), report.OS.as_ref().map(|os| os.InstallDate.format("%Y-%m-%d %H:%M").to_string())),
+            Self::Activation => labeled(catalog::Message("activation"), report.OS.as_ref().and_then(|os| os.ActivationStatus).map(|status| status.to_string())),
+            Self::HostSystem => labeled(catalog::Message("host"), report.HostSystem.as_ref().map(ToString::to_string)),
+            Self::Kernel => labeled(catalog::Message("kernel"), report.Kernel.as_ref().map(ToString::to_string)),
+            Self::Motherboard => labeled(catalog::Message("motherboard"), report.Motherboard.as_ref().map(ToString::to_string)),
+            Self::Bios => labeled(catalog::Message("bios"), report.Bios.as_ref().map(ToString::to_string)),
+            Self::Virtualization => labeled(catalog::Message("virtualization"), report.Virtualization.as_ref().map(ToString::to_string)),
+            Self::Uptime => labeled(catalog::Message("uptime"), report.Uptime.as_ref().map(ToString::to_string)),
+            Self::ScreenResolution => labeled(
+                catalog::Plural("resolution", report.ScreenResolution.as_ref().map_or(1, |screen_resolution| screen_resolution.Monitors.len() as u64)),
+                report.ScreenResolution.as_ref().map(ToString::to_string)
+            ),
+            Self::Processor => labeled(catalog::Message("cpu"), report.Processor.as_ref().map(ToString::to_string)),
+            Self::GraphicsCard => labeled(
+                catalog::Plural("gpu", report.GraphicsCard.as_ref().map_or(1, |graphics_card| graphics_card.Names.len() as u64)),
+                report.GraphicsCard.as_ref().map(ToString::to_string)
+            ),
+            Self::ProcessorUsage => labeled(catalog::Message("cpu_usage"), report.ProcessorUsage.as_ref().map(ToString::to_string)),
+            Self::ProcessorCores => labeled(catalog::Message("cpu_cores"), report.ProcessorUsage.as_ref().map(|processor_usage| match processor_usage.Cores.is_empty() {
+                true => catalog::Message("unavailable"),
+                false => processor_usage.CoreBars()
+            })),
+            Self::Memory => labeled(catalog::Message("memory"), report.Memory.as_ref().map(ToString::to_string)),
+            Self::Temperature => labeled(catalog::Message("temperature"), report.Temperature.as_ref().map(ToString::to_string)),
+            Self::Battery => report.Battery
+                .iter()
+                .flat_map(|battery| &battery.Batteries)
+                .map(|battery| InfoLine::Labeled(catalog::Message("battery"), battery.to_string()))
+                .collect(),
+            Self::Storage => report.Storage
+                .iter()
+                .flat_map(|storage| &storage.Drives)
+                .map(|drive| InfoLine::Drive(drive.DriveLetter.clone(), drive.to_string()))
+                .collect(),
+            Self::Wsl => report.Wsl
+                .iter()
+                .flat_map(|wsl| &wsl.Distributions)
+                .map(|distribution| InfoLine::Distribution(distribution.DistributionName.clone(), distribution.to_string()))
+                .collect(),
+            Self::Locale => labeled(catalog::Message("locale"), report.Locale.as_ref().map(ToString::to_string)),
+            Self::Users => labeled(catalog::Message("users"), report.Users.as_ref().map(ToString::to_string)),
+            Self::Top => labeled(catalog::Message("top"), report.Processes.as_ref().map(ToString::to_string)),
+            Self::Custom => report.Custom
+                .iter()
+                .flatten()
+                .map(|custom_value| InfoLine::Custom(custom_value.Label.clone(), custom_value.to_string()))
+                .collect()
+        }
+    }
+}
+
+pub enum InfoLine {
+    Labeled(String, String),
+    Drive(String, String),
+    Distribution(String, String),
+    Custom(String, String),
+    Unlabeled(String)
+}
+
+impl InfoLine {
+    pub fn Label(&self) -> Option<String> {
+        match self {
+            Self::Labeled(label, _) => Some(label.clone()),
+            Self::Drive(drive_letter, _) => Some(format!("{} ({})", catalog::Message("drive"), drive_letter)),
+            Self::Distribution(distribution_name, _) => Some(format!("WSL ({})", distribution_name)),
+            Self::Custom(label, _) => Some(label.clone()),
+            Self::Unlabeled(_) => None
+        }
+    }
+
+    pub fn WithValue(self, value: String) -> Self {
+        match self {
+            Self::Labeled(label, _) => Self::Labeled(label, value),
+            Self::Drive(drive_letter, _) => Self::Drive(drive_letter, value),
+            Self::Distribution(distribution_name, _) => Self::Distribution(distribution_name, value),
+            Self::Custom(label, _) => Self::Custom(label, value),
+            Self::Unlabeled(_) => Self::Unlabeled(value)
+        }
+    }
+
+    pub fn Value(&self) -> &str {
+        match self {
+            Self::Labeled(_, value) | Self::Drive(_, value) | Self::Distribution(_, value) | Self::Custom(_, value) | Self::Unlabeled(value) => value
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    collections::BTreeMap,
+    time::Duration
+};
+
+use serde::Serialize;
+
+use crate::winfetch::{
+    error::{
+        WinfetchError,
+        WinfetchResult
+    },
+    model::{
+        battery::Battery,
+        bios::Bios,
+        custom::{
+            CustomModule,
+            CustomValue
+        },
+        graphicscard::GraphicsCard,
+        hostsystem::HostSystem,
+        locale::Locale,
+        memory::Memory,
+        motherboard::Motherboard,
+        names::Names,
+        os::OS,
+        processes::{
+            self,
+            ProcessSort,
+            Processes
+        },
+        processor::Processor,
+        processorusage::{
+            self,
+            ProcessorUsage
+        },
+        screenres::ScreenResolution,
+        storage::{
+            Storage,
+            StorageFilter
+        },
+        temperature::Temperature,
+        uptime::SystemUptime,
+        users::Users,
+        virtualization::Virtualization,
+        winntkernel::WindowsNTKernel,
+        wsl::Wsl
+    },
+    module::Module
+};
+
+/// Selects what `Collect` gathers.
+pub struct Options {
+    /// The modules to collect; the fields of the report not needed by any of them are left as `None`.
+    pub Modules: Vec<Module>,
+    /// How long the processor usage is sampled for.
+    pub SampleInterval: Duration,
+    /// How many processes the `top` module lists.
+    pub TopProcesses: usize,
+    /// Whether the `top` module lists the processes with the most cpu time or the largest working set.
+    pub ProcessSort: ProcessSort,
+    /// The drives the `storage` module shows.
+    pub StorageFilter: StorageFilter,
+    /// Whether the `storage` module shows the physical disk of every drive, which takes a few more queries.
+    pub PhysicalDisks: bool,
+    /// The lines of the `custom` module.
+    pub CustomModules: Vec<CustomModule>
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            Modules: Module::DefaultModules(),
+            SampleInterval: processorusage::DEFAULT_SAMPLE_INTERVAL,
+            TopProcesses: processes::DEFAULT_TOP_PROCESSES,
+            ProcessSort: ProcessSort::Cpu,
+            StorageFilter: StorageFilter::default(),
+            PhysicalDisks: false,
+            CustomModules: Vec::new()
+        }
+    }
+}
+
+/// Everything winfetch knows about the system.
+#[derive(Default, Serialize)]
+pub struct SystemReport {
+    /// The user and computer names, used by the `title` and `underline` modules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Names: Option<Names>,
+    /// The product name, version, edition, install date and, for the `activation` module, the activation status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub OS: Option<OS>,
+    /// The manufacturer and model of the computer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub HostSystem: Option<HostSystem>,
+    /// The version of the Windows NT kernel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Kernel: Option<WindowsNTKernel>,
+    /// The manufacturer and product of the motherboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Motherboard: Option<Motherboard>,
+    /// The firmware version, release date, boot mode and Secure Boot state.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Bios: Option<Bios>,
+    /// The hypervisor or cloud the system runs under, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Virtualization: Option<Virtualization>,
+    /// The last boot time and the time elapsed since.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Uptime: Option<SystemUptime>,
+    /// The resolution of every monitor, in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ScreenResolution: Option<ScreenResolution>,
+    /// The name of the processor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Processor: Option<Processor>,
+    /// The names of the graphics cards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub GraphicsCard: Option<GraphicsCard>,
+    /// The processor load sampled over `Options::SampleInterval`, in total, per core and per socket, and the number of running processes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ProcessorUsage: Option<ProcessorUsage>,
+    /// The free and total physical memory, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Memory: Option<Memory>,
+    /// The temperature of every thermal zone, in degrees Celsius.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Temperature: Option<Temperature>,
+    /// The charge and state of every battery, and the time left on the ones discharging.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Battery: Option<Battery>,
+    /// The free and total space of every logical drive, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Storage: Option<Storage>,
+    /// The installed WSL distributions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Wsl: Option<Wsl>,
+    /// The display language, regional format, time zone and configured keyboard layouts of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Locale: Option<Locale>,
+    /// The console and remote desktop sessions of the logged-in users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Users: Option<Users>,
+    /// The number of processes and threads, and the heaviest processes with their cpu time and working set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Processes: Option<Processes>,
+    /// The values of the user-defined lines, or why they could not be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub Custom: Option<Vec<CustomValue>>,
+    /// Why the modules that could not be collected are missing; a module failing again on a refresh keeps its previous values.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub Errors: BTreeMap<Module, String>
+}
+
+/// Gathers the parts of the report needed by the modules in `options`.
+pub fn Collect(options: &Options) -> WinfetchResult<SystemReport> {
+    let mut report = SystemReport::default();
+    CollectModules(&mut report, &options.Modules, options)?;
+
+    Ok(report)
+}
+
+/// Gathers again the parts of the report that change while the system is running, like the memory usage.
+pub fn Refresh(report: &mut SystemReport, options: &Options) -> WinfetchResult<()> {
+    let modules = options.Modules.iter().copied().filter(Module::IsDynamic).collect::<Vec<_>>();
+
+    CollectModules(report, &modules, options)
+}
+
+// a module that fails is left out of the report, instead of failing the whole report
+fn CollectModules(report: &mut SystemReport, modules: &[Module], options: &Options) -> WinfetchResult<()> {
+    // the `cpu_usage` and `cpu_cores` modules share one sample
+    let mut processor_usage_sampled = false;
+
+    for &module in modules {
+        match CollectModule(report, module, options, &mut processor_usage_sampled) {
+            Ok(()) => {
+                report.Errors.remove(&module);
+            },
+            Err(WinfetchError(error)) => {
+                report.Errors.insert(module, error);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn CollectModule(report: &mut SystemReport, module: Module, options: &Options, processor_usage_sampled: &mut bool) -> WinfetchResult<()> {
+    match module {
+        Module::Title | Module::Underline => if report.Names.is_none() {
+            report.Names = Some(Names::GetNames());
+        },
+        Module::OS | Module::Edition | Module::InstallDate | Module::Activation => {
+            if report.OS.is_none() {
+                let mut operating_system = OS::GetOperatingSystemVersion()?;
+                operating_system.GetOperatingSystemArchitecture()?;
+
+                report.OS = Some(operating_system);
+            }
+
+            // querying the licensing service is slow, so it is only done when asked for
+            if let (Module::Activation, Some(operating_system)) = (module, &mut report.OS) {
+                if operating_system.ActivationStatus.is_none() {
+                    operating_system.GetActivationStatus()?;
+                }
+            }
+        },
+        Module::HostSystem => report.HostSystem = Some(HostSystem::GetHostSystemInformation()?),
+        Module::Kernel => report.Kernel = Some(WindowsNTKernel::GetCurrentWindowsNTKernelVersion()?),
+        Module::Motherboard => report.Motherboard = Some(Motherboard::GetMotherboard()?),
+        Module::Bios => report.Bios = Some(Bios::GetBios()?),
+        Module::Virtualization => report.Virtualization = Some(Virtualization::GetVirtualization()?),
+        Module::Uptime => report.Uptime = Some(SystemUptime::GetSystemUptime()?),
+        Module::ScreenResolution => report.ScreenResolution = Some(ScreenResolution::GetScreenResolution()?),
+        Module::Processor => report.Processor = Some(Processor::GetProcessor()?),
+        Module::GraphicsCard => report.GraphicsCard = Some(GraphicsCard::GetGraphicsCards()?),
+        Module::ProcessorUsage | Module::ProcessorCores => if !*processor_usage_sampled {
+            // marked before sampling, so that a failing sample is not taken twice
+            *processor_usage_sampled = true;
+
+            let mut processor_usage = ProcessorUsage::GetProcessorUsage(options.SampleInterval)?;
+            processor_usage.GetProcessesCount()?;
+
+            report.ProcessorUsage = Some(processor_usage);
+        },
+        Module::Memory => report.Memory = Some(Memory::GetMemoryStatistics()?),
+        Module::Temperature => report.Temperature = Some(Temperature::GetTemperature()?),
+        Module::Battery => report.Battery = Some(Battery::GetBattery()?),
+        Module::Storage => report.Storage = Some(Storage::GetStorageStatistics(&options.StorageFilter, options.PhysicalDisks)?),
+        Module::Wsl => report.Wsl = Some(Wsl::GetWsl()?),
+        Module::Locale => report.Locale = Some(Locale::GetLocale()?),
+        Module::Users => report.Users = Some(Users::GetUsers()?),
+        Module::Top => report.Processes = Some(Processes::GetProcesses(options.ProcessSort, options.TopProcesses)?),
+        Module::Custom => report.Custom = Some(options.CustomModules.iter().map(CustomModule::GetCustomValue).collect())
+    }
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fmt::Write,
+    mem
+};
+
+use chrono::{
+    NaiveDate,
+    NaiveDateTime
+};
+
+use serde::Deserialize;
+
+use crate::winfetch::{
+    __internals,
+    error::{
+        WinfetchError,
+        WinfetchResult
+    },
+    utils
+};
+
+const DEFAULT_DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+const BYTE_UNITS: &[(&str, f64)] = &[
+    ("b", 1.0),
+    ("kb", 1e3),
+    ("mb", 1e6),
+    ("gb", 1e9),
+    ("tb", 1e12),
+    ("kib", 1024.0),
+    ("mib", 1048576.0),
+    ("gib", 1073741824.0),
+    ("tib", 1099511627776.0)
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlaceholderKind {
+    Text,
+    Integer,
+    Bytes,
+    Percent,
+    DateTime
+}
+
+impl PlaceholderKind {
+    // describes the formats accepted after the colon, for error messages
+    fn Formats(&self) -> &'static str {
+        match self {
+            Self::Text => "`lower` or `upper`",
+            Self::Integer => "no format",
+            Self::Bytes => "one of `b`, `kb`, `mb`, `gb`, `tb`, `kib`, `mib`, `gib` or `tib`",
+            Self::Percent => "`bar`",
+            Self::DateTime => "a strftime format like `%Y-%m-%d`"
+        }
+    }
+
+    fn AcceptsFormat(&self, format: &str) -> bool {
+        match self {
+            Self::Text => format == "lower" || format == "upper",
+            Self::Integer => false,
+            Self::Bytes => BYTE_UNITS.iter().any(|(unit, _)| *unit == format),
+            Self::Percent => format == "bar",
+            // a date time without a time zone cannot be formatted with `%z` and the like, which only fails when formatting
+            Self::DateTime => {
+                let sample = NaiveDate::from_ymd_opt(2000, 1, 1).and_then(|date| date.and_hms_opt(0, 0, 0)).unwrap_or_default();
+
+                write!(String::new(), "{}", sample.format(format)).is_ok()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplateValue {
+    Text(String),
+    Integer(u64),
+    Bytes(u64),
+    Percent(f64),
+    DateTime(NaiveDateTime)
+}
+
+impl TemplateValue {
+    fn Render(&self, format: Option<&str>) -> WinfetchResult<String> {
+        Ok(match (self, format) {
+            (Self::Text(text), Some("lower")) => text.to_lowercase(),
+            (Self::Text(text), Some("upper")) => text.to_uppercase(),
+            (Self::Text(text), _) => text.clone(),
+            (Self::Integer(integer), _) => integer.to_string(),
+            (Self::Bytes(bytes), None) => __internals::__InternalsToUnits(*bytes as f64),
+            (Self::Bytes(bytes), Some("b")) => bytes.to_string(),
+            (Self::Bytes(bytes), Some(unit)) => {
+                let divisor = BYTE_UNITS.iter().find(|(name, _)| *name == unit).map_or(1.0, |&(_, divisor)| divisor);
+
+                format!("{:.2}", *bytes as f64 / divisor)
+            },
+            (Self::Percent(percent), Some("bar")) => utils::GeneratePercentageBar(percent.round() as i32),
+            (Self::Percent(percent), _) => format!("{:.0}", percent),
+            (Self::DateTime(date_time), format) => {
+                let format = format.unwrap_or(DEFAULT_DATE_TIME_FORMAT);
+                let mut rendered = String::new();
+
+                write!(rendered, "{}", date_time.format(format)).map_err(|_| WinfetchError(format!("could not format date with `{}`", format)))?;
+
+                rendered
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(String, Option<String>)
+}
+
+// a line template like `{used:gib} / {total:gib} ({percent}%)`; braces are escaped by doubling them
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "String")]
+pub struct Template {
+    Segments: Vec<Segment>
+}
+
+impl Template {
+    pub fn Parse(template: &str) -> WinfetchResult<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                },
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                },
+                '{' => {
+                    let mut placeholder = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => return Err(WinfetchError(format!("unclosed placeholder `{{{}` in template `{}`", placeholder, template))),
+                            Some(char) => placeholder.push(char)
+                        }
+                    }
+
+                    let (key, format) = match placeholder.split_once(':') {
+                        Some((key, format)) => (key.trim(), Some(format.to_string())),
+                        None => (placeholder.trim(), None)
+                    };
+
+                    if key.is_empty() {
+                        return Err(WinfetchError(format!("empty placeholder in template `{}`", template)));
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(mem::take(&mut literal)));
+                    }
+
+                    segments.push(Segment::Placeholder(key.to_string(), format));
+                },
+                '}' => return Err(WinfetchError(format!("unmatched `}}` in template `{}`; use `}}}}` for a literal brace", template))),
+                char => literal.push(char)
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { Segments: segments })
+    }
+
+    // checks every placeholder against the ones the module provides
+    pub fn Validate(&self, placeholders: &[(&str, PlaceholderKind)]) -> WinfetchResult<()> {
+        for segment in &self.Segments {
+            if let Segment::Placeholder(key, format) = segment {
+                let kind = match placeholders.iter().find(|(name, _)| name == key) {
+                    Some(&(_, kind)) => kind,
+                    None if placeholders.is_empty() => return Err(WinfetchError(format!("unknown placeholder `{}`; no placeholders are available", key))),
+                    None => return Err(WinfetchError(format!(
+                        "unknown placeholder `{}`; available placeholders: {}",
+                        key,
+                        placeholders.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+                    )))
+                };
+
+                if let Some(format) = format {
+                    if !kind.AcceptsFormat(format) {
+                        return Err(WinfetchError(format!("invalid format `{}` for placeholder `{}`; expected {}", format, key, kind.Formats())));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // placeholders without a value are rendered as empty strings
+    pub fn Render(&self, values: &BTreeMap<&str, TemplateValue>) -> WinfetchResult<String> {
+        self.Segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => Ok(literal.clone()),
+                Segment::Placeholder(key, format) => values
+                    .get(&**key)
+                    .map_or_else(|| Ok(String::new()), |value| value.Render(format.as_deref()))
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<String> for Template {
+    type Error = WinfetchError;
+
+    fn try_from(template: String) -> WinfetchResult<Self> {
+        Self::Parse(&template)
+    }
+}
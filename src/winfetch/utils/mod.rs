@@ -1,74 +1,406 @@
-use crate::winfetch::error::{
-    WinfetchError,
-    WinfetchResult
-};
-
-pub const ANSI_ESCAPE_SEQUENCE: &str = "\x1B[";
-
-pub fn GeneratePercentageBar(percentage: i32) -> WinfetchResult<String> {
-    if !(0..=100).contains(&percentage) {
-        return Err(WinfetchError(format!("invalid percentage value; expected a value between 1 and 100 (inclusive), got {}", percentage)));
-    }
-
-    let mut percent_bar = String::from("[ ");
-    let squares = percentage / 10;
-
-    for i in 1..=squares {
-        if i <= 6 {
-            percent_bar.push_str(&format!("{}32m■{}0m", ANSI_ESCAPE_SEQUENCE, ANSI_ESCAPE_SEQUENCE));
-        }
-        else if i <= 8 {
-            percent_bar.push_str(&format!("{}93m■{}0m", ANSI_ESCAPE_SEQUENCE, ANSI_ESCAPE_SEQUENCE));
-        }
-        else {
-            percent_bar.push_str(&format!("{}91m■{}0m", ANSI_ESCAPE_SEQUENCE, ANSI_ESCAPE_SEQUENCE))
-        }
-    }
-
-    percent_bar.push_str(&"-".repeat(10 - squares as usize));
-    percent_bar.push_str(" ]");
-
-    Ok(percent_bar)
-}
-
-pub fn GetWindowsASCIIArt() -> String {
-    format!(
-        " {}34m                    ....,,:;+ccllll
- {}34m      ...,,+:;  cllllllllllllllllll
- {}34m,cclllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34m
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34mllllllllllllll  lllllllllllllllllll
- {}34m`'ccllllllllll  lllllllllllllllllll
- {}34m      `' \\\\*::  :ccllllllllllllllll
- {}34m                       ````''*::cll
- {}34m                                 `` {}0m",
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE,
-        ANSI_ESCAPE_SEQUENCE
-    )
-}
+pub const ANSI_ESCAPE_SEQUENCE: &str = "\x1B[";
+
+pub const LOGO_NAMES: &[&str] = &["windows", "small"];
+
+// percentages outside of 0 to 100 are drawn as an empty or a full bar
+pub fn GeneratePercentageBar(percentage: i32) -> String {
+    let percentage = percentage.clamp(0, 100);
+
+    let mut percent_bar = String::from("[ ");
+    let squares = percentage / 10;
+
+    for i in 1..=squares {
+        if i <= 6 {
+            percent_bar.push_str(&format!("{}32m■{}0m", ANSI_ESCAPE_SEQUENCE, ANSI_ESCAPE_SEQUENCE));
+        }
+        else if i <= 8 {
+            percent_bar.push_str(&format!("{}93m■{}0m", ANSI_ESCAPE_SEQUENCE, ANSI_ESCAPE_SEQUENCE));
+        }
+        else {
+            percent_bar.push_str(&format!("{}91m■{}0m", ANSI_ESCAPE_SEQUENCE, ANSI_ESCAPE_SEQUENCE))
+        }
+    }
+
+    percent_bar.push_str(&"-".repeat(10 - squares as usize));
+    percent_bar.push_str(" ]");
+
+    percent_bar
+}
+
+// a single block whose height follows the percentage, colored like the squares of the percentage bar
+pub fn GenerateMiniBar(percentage: f64) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let percentage = percentage.clamp(0.0, 100.0);
+    let block = BLOCKS[(percentage / 100.0 * 7.0).round() as usize];
+    let color = if percentage > 80.0 {
+        "91m"
+    }
+    else if percentage > 60.0 {
+        "93m"
+    }
+    else {
+        "32m"
+    };
+
+    format!("{}{}{}{}0m", ANSI_ESCAPE_SEQUENCE, color, block, ANSI_ESCAPE_SEQUENCE)
+}
+
+pub fn StripANSIEscapeSequences(string: &str) -> String {
+    let mut stripped = String::with_capacity(string.len());
+    let mut chars = string.chars();
+
+    while let Some(char) = chars.next() {
+        if char == '\x1B' {
+            if chars.next() == Some('[') {
+                // skip the parameter and intermediate bytes up to and including the final byte
+                for sequence_char in chars.by_ref() {
+                    if ('\x40'..='\x7E').contains(&sequence_char) {
+                        break;
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        stripped.push(char);
+    }
+
+    stripped
+}
+
+// the number of terminal columns a character takes; combining marks take none and east asian wide characters take two
+fn CharWidth(char: char) -> usize {
+    match char as u32 {
+        0x00..=0x1F | 0x7F..=0x9F => 0,
+        0x0300..=0x036F | 0x0483..=0x0489 | 0x0591..=0x05BD | 0x0610..=0x061A | 0x064B..=0x065F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F | 0x20D0..=0x20FF | 0xFE00..=0xFE0F | 0xFE20..=0xFE2F | 0xE0100..=0xE01EF => 0,
+        0x1100..=0x115F | 0x2E80..=0x303E | 0x3041..=0x33FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xA000..=0xA4CF | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x20000..=0x2FFFD | 0x30000..=0x3FFFD => 2,
+        // the pictographs, emoticons, transport and map symbols, and the supplemental and extended pictographs
+        0x1F300..=0x1F64F | 0x1F680..=0x1F6FF | 0x1F900..=0x1F9FF | 0x1FA70..=0x1FAFF => 2,
+        _ => 1
+    }
+}
+
+pub fn VisibleWidth(string: &str) -> usize {
+    StripANSIEscapeSequences(string).chars().map(CharWidth).sum()
+}
+
+// splits a string after at most `width` visible columns, keeping the escape sequences in the first part
+pub fn SplitAtVisibleWidth(string: &str, width: usize) -> (&str, &str) {
+    let mut visible_width = 0;
+    let mut chars = string.char_indices().peekable();
+
+    while let Some((index, char)) = chars.next() {
+        if char == '\x1B' {
+            if let Some((_, '[')) = chars.peek() {
+                chars.next();
+
+                for (_, sequence_char) in chars.by_ref() {
+                    if ('\x40'..='\x7E').contains(&sequence_char) {
+                        break;
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        if visible_width + CharWidth(char) > width {
+            return string.split_at(index);
+        }
+
+        visible_width += CharWidth(char);
+    }
+
+    (string, "")
+}
+
+// cuts a string to `width` visible columns, ending it with an ellipsis when something was cut
+pub fn TruncateVisible(string: &str, width: usize) -> String {
+    if VisibleWidth(string) <= width {
+        return string.to_string();
+    }
+
+    let (head, _) = SplitAtVisibleWidth(string, width.saturating_sub(1));
+
+    // the colors of the cut part must not spill over the ellipsis
+    if head.contains('\x1B') {
+        format!("{}{}0m…", head, ANSI_ESCAPE_SEQUENCE)
+    }
+    else {
+        format!("{}…", head)
+    }
+}
+
+// breaks a string into lines of at most `width` visible columns, preferring to break after a space
+pub fn WrapVisible(string: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = string;
+
+    while VisibleWidth(rest) > width && width > 0 {
+        let (head, _) = SplitAtVisibleWidth(rest, width);
+        let break_index = match head.rfind(' ') {
+            Some(index) if !StripANSIEscapeSequences(&head[..index]).trim().is_empty() => index + 1,
+            // a character wider than the line still has to go somewhere
+            _ if head.is_empty() => rest.chars().next().map_or(rest.len(), char::len_utf8),
+            _ => head.len()
+        };
+
+        lines.push(rest[..break_index].trim_end().to_string());
+        rest = rest[break_index..].trim_start();
+    }
+
+    lines.push(rest.to_string());
+
+    lines
+}
+
+fn ANSIColorToHTML(code: u32) -> Option<&'static str> {
+    Some(match code {
+        30 => "#000000",
+        31 => "#cd3131",
+        32 => "#0dbc79",
+        33 => "#e5e510",
+        34 => "#2472c8",
+        35 => "#bc3fbc",
+        36 => "#11a8cd",
+        37 => "#e5e5e5",
+        90 => "#666666",
+        91 => "#f14c4c",
+        92 => "#23d18b",
+        93 => "#f5f543",
+        94 => "#3b8eea",
+        95 => "#d670d6",
+        96 => "#29b8db",
+        97 => "#ffffff",
+        _ => return None
+    })
+}
+
+pub fn EscapeHTML(string: &str) -> String {
+    string
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// the 256 color palette: the 16 standard colors, a 6x6x6 color cube and 24 shades of gray
+fn ANSI256ColorToHTML(index: u32) -> Option<String> {
+    const CUBE_LEVELS: [u32; 6] = [0, 95, 135, 175, 215, 255];
+
+    match index {
+        0..=7 => ANSIColorToHTML(30 + index).map(String::from),
+        8..=15 => ANSIColorToHTML(90 + index - 8).map(String::from),
+        16..=231 => {
+            let index = index - 16;
+
+            Some(format!("#{:02x}{:02x}{:02x}", CUBE_LEVELS[(index / 36) as usize], CUBE_LEVELS[(index / 6 % 6) as usize], CUBE_LEVELS[(index % 6) as usize]))
+        },
+        232..=255 => {
+            let level = 8 + (index - 232) * 10;
+
+            Some(format!("#{:02x}{:02x}{:02x}", level, level, level))
+        },
+        _ => None
+    }
+}
+
+// reads the color of a `38` or `48` parameter: `5;<index>` or `2;<red>;<green>;<blue>`
+fn ExtendedColorToHTML<I: Iterator<Item = u32>>(codes: &mut I) -> Option<String> {
+    match codes.next()? {
+        5 => ANSI256ColorToHTML(codes.next()?),
+        2 => {
+            let (red, green, blue) = (codes.next()?, codes.next()?, codes.next()?);
+
+            Some(format!("#{:02x}{:02x}{:02x}", red.min(255), green.min(255), blue.min(255)))
+        },
+        _ => None
+    }
+}
+
+// converts the sgr sequences setting the foreground color and the intensity to styled spans; other escape sequences are dropped
+pub fn ANSIToHTML(string: &str) -> String {
+    let mut html = String::with_capacity(string.len());
+    let mut text = String::new();
+    let mut chars = string.chars();
+    let mut color = None;
+    let mut bold = false;
+    let mut span_open = false;
+
+    while let Some(char) = chars.next() {
+        if char != '\x1B' {
+            text.push(char);
+            continue;
+        }
+
+        html.push_str(&EscapeHTML(&text));
+        text.clear();
+
+        if chars.next() != Some('[') {
+            continue;
+        }
+
+        let mut parameters = String::new();
+        let mut final_byte = None;
+
+        for sequence_char in chars.by_ref() {
+            if ('\x40'..='\x7E').contains(&sequence_char) {
+                final_byte = Some(sequence_char);
+                break;
+            }
+
+            parameters.push(sequence_char);
+        }
+
+        if final_byte != Some('m') {
+            continue;
+        }
+
+        // an empty parameter list is the same as a reset
+        let mut codes = parameters.split(';').map(|code| code.parse::<u32>().unwrap_or(0));
+
+        while let Some(code) = codes.next() {
+            match code {
+                0 => {
+                    color = None;
+                    bold = false;
+                },
+                1 => bold = true,
+                22 => bold = false,
+                38 => if let Some(html_color) = ExtendedColorToHTML(&mut codes) {
+                    color = Some(html_color);
+                },
+                39 => color = None,
+                // the background is not shown, but its color parameters must not be read as codes
+                48 => {
+                    ExtendedColorToHTML(&mut codes);
+                },
+                code => if let Some(html_color) = ANSIColorToHTML(code) {
+                    color = Some(html_color.to_string());
+                }
+            }
+        }
+
+        if span_open {
+            html.push_str("</span>");
+            span_open = false;
+        }
+
+        let mut style = Vec::new();
+
+        if let Some(color) = &color {
+            style.push(format!("color:{}", color));
+        }
+
+        if bold {
+            style.push(String::from("font-weight:bold"));
+        }
+
+        if !style.is_empty() {
+            html.push_str(&format!("<span style=\"{}\">", style.join(";")));
+            span_open = true;
+        }
+    }
+
+    html.push_str(&EscapeHTML(&text));
+
+    if span_open {
+        html.push_str("</span>");
+    }
+
+    html
+}
+
+pub fn GetSmallWindowsASCIIArt() -> String {
+    format!(
+        " {}34mlllllll  lllllll
+ {}34mlllllll  lllllll
+ {}34mlllllll  lllllll
+ {}34m
+ {}34mlllllll  lllllll
+ {}34mlllllll  lllllll
+ {}34mlllllll  lllllll {}0m",
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE
+    )
+}
+
+pub fn GetLogo(name: &str) -> Option<String> {
+    match name {
+        "windows" => Some(GetWindowsASCIIArt()),
+        "small" => Some(GetSmallWindowsASCIIArt()),
+        _ => None
+    }
+}
+
+pub fn GetWindowsASCIIArt() -> String {
+    format!(
+        " {}34m                    ....,,:;+ccllll
+ {}34m      ...,,+:;  cllllllllllllllllll
+ {}34m,cclllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34m
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34mllllllllllllll  lllllllllllllllllll
+ {}34m`'ccllllllllll  lllllllllllllllllll
+ {}34m      `' \\\\*::  :ccllllllllllllllll
+ {}34m                       ````''*::cll
+ {}34m                                 `` {}0m",
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE,
+        ANSI_ESCAPE_SEQUENCE
+    )
+}
+
+// converts a windows path like `C:\Users\me` to its `/mnt/c/Users/me` form
+pub fn ToMntPath(path: &str) -> String {
+    let path = path.strip_prefix(r"\\?\").unwrap_or(path);
+    let mut chars = path.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(drive_letter), Some(':')) if drive_letter.is_ascii_alphabetic() => {
+            let rest = chars.as_str().trim_start_matches('\\').replace('\\', "/");
+
+            if rest.is_empty() {
+                format!("/mnt/{}", drive_letter.to_ascii_lowercase())
+            }
+            else {
+                format!("/mnt/{}/{}", drive_letter.to_ascii_lowercase(), rest)
+            }
+        },
+        _ => path.replace('\\', "/")
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    io::{
+        self,
+        Write
+    },
+    sync::atomic::{
+        AtomicBool,
+        Ordering
+    },
+    thread,
+    time::{
+        Duration,
+        Instant
+    }
+};
+
+#[cfg(windows)]
+use winapi::{
+    shared::minwindef::{
+        BOOL,
+        DWORD,
+        FALSE,
+        TRUE
+    },
+    um::{
+        consoleapi::SetConsoleCtrlHandler as Win32_SetConsoleCtrlHandler,
+        wincon::{
+            CTRL_BREAK_EVENT,
+            CTRL_C_EVENT,
+            CTRL_CLOSE_EVENT
+        }
+    }
+};
+
+use crate::winfetch::{
+    error::{
+        WinfetchError,
+        WinfetchResult
+    },
+    report::{
+        self,
+        Options,
+        SystemReport
+    },
+    utils
+};
+
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+// how often the ctrl+c flag is checked while waiting for the next refresh
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(windows)]
+unsafe extern "system" fn CtrlHandler(ctrl_type: DWORD) -> BOOL {
+    match ctrl_type {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT | CTRL_CLOSE_EVENT => {
+            INTERRUPTED.store(true, Ordering::SeqCst);
+            TRUE
+        },
+        _ => FALSE
+    }
+}
+
+#[cfg(not(windows))]
+extern "C" fn SignalHandler(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+#[cfg(windows)]
+fn SetInterruptHandler() -> bool {
+    unsafe { Win32_SetConsoleCtrlHandler(Some(CtrlHandler), TRUE) != 0 }
+}
+
+// replayed dumps can be watched on other platforms as well
+#[cfg(not(windows))]
+fn SetInterruptHandler() -> bool {
+    let handler = SignalHandler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+
+    unsafe { libc::signal(libc::SIGINT, handler) != libc::SIG_ERR && libc::signal(libc::SIGTERM, handler) != libc::SIG_ERR }
+}
+
+fn WaitInterruptible(interval: Duration) -> bool {
+    let start = Instant::now();
+
+    while start.elapsed() < interval {
+        if INTERRUPTED.load(Ordering::SeqCst) {
+            return false;
+        }
+
+        thread::sleep(POLL_INTERVAL.min(interval.saturating_sub(start.elapsed())));
+    }
+
+    !INTERRUPTED.load(Ordering::SeqCst)
+}
+
+fn Draw(lines: &[String]) -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    // redraw from the top left corner, clearing what is left of the previous frame
+    write!(stdout, "{}H", utils::ANSI_ESCAPE_SEQUENCE)?;
+
+    for line in lines {
+        writeln!(stdout, "{}{}K", line, utils::ANSI_ESCAPE_SEQUENCE)?;
+    }
+
+    write!(stdout, "{}J", utils::ANSI_ESCAPE_SEQUENCE)?;
+    stdout.flush()
+}
+
+// redraws the report until ctrl+c is pressed; only the dynamic modules are collected again on every refresh
+pub fn Watch<F>(report: &mut SystemReport, options: &Options, interval: Duration, render: F) -> WinfetchResult<()>
+where
+    F: Fn(&SystemReport) -> Vec<String>
+{
+    if !SetInterruptHandler() {
+        return Err(WinfetchError(String::from("could not set console control handler")));
+    }
+
+    // switch to the alternate screen and hide the cursor
+    print!("{}?1049h{}?25l", utils::ANSI_ESCAPE_SEQUENCE, utils::ANSI_ESCAPE_SEQUENCE);
+
+    let mut result = Ok(());
+
+    loop {
+        if let Err(error) = Draw(&render(report)) {
+            result = Err(WinfetchError(format!("could not write to the terminal; error: {}", error)));
+            break;
+        }
+
+        if !WaitInterruptible(interval) {
+            break;
+        }
+
+        // a failed refresh keeps the previous values on screen, and the next tick tries again
+        report::Refresh(report, options).ok();
+    }
+
+    // restore the cursor and the main screen
+    print!("{}?25h{}?1049l", utils::ANSI_ESCAPE_SEQUENCE, utils::ANSI_ESCAPE_SEQUENCE);
+    io::stdout().flush().ok();
+
+    result
+}
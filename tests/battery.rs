@@ -0,0 +1,64 @@
+#![allow(non_snake_case)]
+
+use std::time::Duration;
+
+use linux_commands_on_windows::winfetch::{
+    model::battery::{
+        BatteryInfo,
+        BatteryState
+    },
+    utils
+};
+
+// the rows are written in the format of `--dump`, since the wmi types are internal
+fn FromRow(row: &str) -> Option<BatteryInfo> {
+    BatteryInfo::FromBattery(&serde_json::from_str(row).unwrap())
+}
+
+#[test]
+fn battery_statuses_are_folded_into_states() {
+    let cases = [
+        (1, BatteryState::Discharging),
+        (2, BatteryState::PluggedIn),
+        (3, BatteryState::Full),
+        (4, BatteryState::Discharging),
+        (5, BatteryState::Discharging),
+        (6, BatteryState::Charging),
+        (9, BatteryState::Charging),
+        (10, BatteryState::Unknown),
+        (11, BatteryState::PluggedIn),
+        (0, BatteryState::Unknown)
+    ];
+
+    for (battery_status, state) in cases {
+        assert_eq!(BatteryState::FromBatteryStatus(battery_status), state, "{}", battery_status);
+    }
+}
+
+#[test]
+fn the_remaining_time_is_only_kept_while_discharging() {
+    let discharging = FromRow(r#"{"Name": "DELL 7FHHV ", "BatteryStatus": 1, "EstimatedChargeRemaining": 64, "EstimatedRunTime": 135}"#).unwrap();
+    let plugged_in = FromRow(r#"{"Name": "DELL 7FHHV", "BatteryStatus": 2, "EstimatedChargeRemaining": 64, "EstimatedRunTime": 135}"#).unwrap();
+    let unknown = FromRow(r#"{"Name": "DELL 7FHHV", "BatteryStatus": 1, "EstimatedChargeRemaining": 64, "EstimatedRunTime": 71582788}"#).unwrap();
+
+    assert_eq!(discharging.Name, "DELL 7FHHV");
+    assert_eq!(discharging.RemainingTime, Some(Duration::from_secs(135 * 60)));
+    assert_eq!(plugged_in.RemainingTime, None);
+    assert_eq!(unknown.RemainingTime, None);
+}
+
+#[test]
+fn batteries_without_a_charge_are_left_out() {
+    assert!(FromRow(r#"{"Name": "DELL 7FHHV", "BatteryStatus": 1, "EstimatedChargeRemaining": null}"#).is_none());
+    assert_eq!(FromRow(r#"{"Name": "DELL 7FHHV", "BatteryStatus": 3, "EstimatedChargeRemaining": 101}"#).unwrap().ChargePercentage, 100);
+}
+
+#[test]
+fn batteries_show_the_charge_state_and_remaining_time() {
+    let discharging = FromRow(r#"{"Name": "DELL 7FHHV", "BatteryStatus": 1, "EstimatedChargeRemaining": 15, "EstimatedRunTime": 65}"#).unwrap();
+    let charging = FromRow(r#"{"Name": "DELL 7FHHV", "BatteryStatus": 6, "EstimatedChargeRemaining": 80, "EstimatedRunTime": 71582788}"#).unwrap();
+
+    assert_eq!(utils::StripANSIEscapeSequences(&discharging.to_string()), "15% (Discharging, 1:05 remaining)");
+    assert!(discharging.to_string().contains("91m"));
+    assert_eq!(utils::StripANSIEscapeSequences(&charging.to_string()), "80% (Charging)");
+}
@@ -0,0 +1,100 @@
+use linux_commands_on_windows::winfetch::catalog::{
+    self,
+    Catalog,
+    Messages,
+    PluralCategory
+};
+
+#[test]
+fn every_catalog_parses_and_has_the_english_ids() {
+    let english = Catalog::LoadCatalog("en").unwrap();
+
+    for language in catalog::LANGUAGES {
+        let catalog = Catalog::LoadCatalog(language).unwrap();
+
+        for id in english.Messages.keys() {
+            assert!(catalog.Messages.contains_key(id), "`{}` is missing message `{}`", language, id);
+        }
+
+        for id in english.Plurals.keys() {
+            assert!(catalog.Plurals.contains_key(id), "`{}` is missing plural `{}`", language, id);
+        }
+    }
+}
+
+#[test]
+fn unknown_catalogs_are_errors() {
+    assert!(Catalog::LoadCatalog("xx").is_err());
+    assert!(Catalog::ParseCatalog("[messages]\nos = 1").is_err());
+}
+
+#[test]
+fn languages_are_resolved_from_locale_names() {
+    assert_eq!(catalog::ResolveLanguage("de"), Some("de"));
+    assert_eq!(catalog::ResolveLanguage("de-AT"), Some("de"));
+    assert_eq!(catalog::ResolveLanguage("fr_CA.UTF-8"), Some("fr"));
+    assert_eq!(catalog::ResolveLanguage("zh-Hans-CN"), Some("zh"));
+    assert_eq!(catalog::ResolveLanguage("JA-jp"), Some("ja"));
+    assert_eq!(catalog::ResolveLanguage("C.UTF-8"), None);
+    assert_eq!(catalog::ResolveLanguage(""), None);
+}
+
+#[test]
+fn unknown_languages_fall_back_to_english() {
+    let messages = Messages::ForLanguage("pt-BR");
+
+    assert_eq!(messages.Language, "en");
+    assert_eq!(messages.Message("uptime"), "System Uptime");
+}
+
+#[test]
+fn missing_messages_fall_back_to_english_then_to_the_id() {
+    let mut messages = Messages::ForLanguage("de");
+    messages.Catalog = Catalog::ParseCatalog("[messages]\nmemory = \"Speicher\"").unwrap();
+
+    assert_eq!(messages.Message("memory"), "Speicher");
+    assert_eq!(messages.Message("kernel"), "Kernel");
+    assert_eq!(messages.Message("no_such_message"), "no_such_message");
+    assert_eq!(messages.Plural("day", 2), "2 days");
+    assert_eq!(messages.Plural("no_such_plural", 2), "2 no_such_plural");
+}
+
+#[test]
+fn plural_rules_follow_the_language() {
+    assert_eq!(PluralCategory::FromCount("en", 0), PluralCategory::Other);
+    assert_eq!(PluralCategory::FromCount("en", 1), PluralCategory::One);
+    assert_eq!(PluralCategory::FromCount("de", 2), PluralCategory::Other);
+    assert_eq!(PluralCategory::FromCount("fr", 0), PluralCategory::One);
+    assert_eq!(PluralCategory::FromCount("fr", 1), PluralCategory::One);
+    assert_eq!(PluralCategory::FromCount("fr", 2), PluralCategory::Other);
+    assert_eq!(PluralCategory::FromCount("ja", 1), PluralCategory::Other);
+    assert_eq!(PluralCategory::FromCount("zh", 1), PluralCategory::Other);
+}
+
+#[test]
+fn plurals_are_formatted_with_the_count() {
+    assert_eq!(Messages::ForLanguage("en").Plural("day", 1), "1 day");
+    assert_eq!(Messages::ForLanguage("en").Plural("minute", 0), "0 minutes");
+    assert_eq!(Messages::ForLanguage("de").Plural("hour", 3), "3 Stunden");
+    assert_eq!(Messages::ForLanguage("fr").Plural("minute", 0), "0 minute");
+    assert_eq!(Messages::ForLanguage("es").Plural("day", 1), "1 día");
+    assert_eq!(Messages::ForLanguage("ja").Plural("day", 1), "1日");
+    assert_eq!(Messages::ForLanguage("zh").Plural("process", 2), "2 个进程");
+}
+
+#[test]
+fn labels_without_a_count_use_the_plural_forms() {
+    let messages = Messages::ForLanguage("en");
+
+    assert_eq!(messages.Plural("resolution", 1), "Screen Resolution");
+    assert_eq!(messages.Plural("gpu", 2), "Graphics Cards");
+}
+
+#[test]
+fn messages_are_formatted_with_their_arguments() {
+    assert_eq!(Messages::ForLanguage("en").Format("guest", &[("hypervisor", "Xen")]), "Xen guest");
+    assert_eq!(Messages::ForLanguage("es").Format("guest", &[("hypervisor", "Xen")]), "invitado de Xen");
+    assert_eq!(Messages::ForLanguage("ja").Format("remaining", &[("time", "1:05")]), "残り 1:05");
+    // unknown arguments are left out, and placeholders without an argument are kept
+    assert_eq!(Messages::ForLanguage("en").Format("since", &[("other", "x")]), "since {time}");
+}
@@ -0,0 +1,121 @@
+#![allow(non_snake_case)]
+
+use serde_json::{
+    json,
+    Value
+};
+
+use linux_commands_on_windows::winfetch::diff::{
+    self,
+    Change
+};
+
+fn Paths(changes: &[Change]) -> Vec<&str> {
+    changes.iter().map(|change| change.Path.as_str()).collect()
+}
+
+fn Drive(drive_letter: &str, free_space: u64, size: u64) -> Value {
+    json!({ "DriveLetter": drive_letter, "FreeSpace": free_space, "Size": size })
+}
+
+#[test]
+fn identical_reports_have_no_changes() {
+    let report = json!({ "OS": { "CurrentBuild": "22631" }, "Storage": { "Drives": [Drive("C:", 50, 100)] } });
+
+    assert!(diff::DiffReports(&report, &report).is_empty());
+}
+
+#[test]
+fn scalar_changes_are_reported_with_their_path() {
+    let old = json!({ "OS": { "CurrentBuild": "22621", "DisplayVersion": "22H2" } });
+    let new = json!({ "OS": { "CurrentBuild": "22631", "DisplayVersion": "22H2", "EditionID": "Professional" } });
+    let changes = diff::DiffReports(&old, &new);
+
+    assert_eq!(Paths(&changes), ["OS.CurrentBuild", "OS.EditionID"]);
+    assert_eq!(changes[0].Old, Some(json!("22621")));
+    assert_eq!(changes[0].New, Some(json!("22631")));
+    assert_eq!(changes[1].Old, None);
+    assert_eq!(changes[0].Module(), "OS");
+}
+
+#[test]
+fn array_elements_are_matched_by_identity() {
+    // a drive inserted before the others does not turn every later drive into a change
+    let old = json!({ "Storage": { "Drives": [Drive("C:", 50, 100), Drive("E:", 10, 100)] } });
+    let new = json!({ "Storage": { "Drives": [Drive("D:", 5, 10), Drive("C:", 50, 100), Drive("E:", 20, 100)] } });
+    let changes = diff::DiffReports(&old, &new);
+
+    assert_eq!(Paths(&changes), ["Storage.Drives[E:].FreeSpace", "Storage.Drives[D:]"]);
+    assert_eq!(changes[1].Old, None);
+}
+
+#[test]
+fn monitors_distributions_and_sessions_have_identities() {
+    let old = json!({
+        "ScreenResolution": { "Monitors": [{ "DeviceName": "\\\\.\\DISPLAY1", "Width": 1920 }, { "DeviceName": "\\\\.\\DISPLAY2", "Width": 1920 }] },
+        "Wsl": { "Distributions": [{ "DistributionName": "Debian", "Version": 1 }, { "DistributionName": "Ubuntu", "Version": 2 }] },
+        "Users": { "Sessions": [{ "SessionId": 1, "UserName": "alex" }] }
+    });
+    let new = json!({
+        "ScreenResolution": { "Monitors": [{ "DeviceName": "\\\\.\\DISPLAY2", "Width": 2560 }, { "DeviceName": "\\\\.\\DISPLAY1", "Width": 1920 }] },
+        "Wsl": { "Distributions": [{ "DistributionName": "Ubuntu", "Version": 2 }] },
+        "Users": { "Sessions": [{ "SessionId": 3, "UserName": "sam" }, { "SessionId": 1, "UserName": "alex" }] }
+    });
+
+    assert_eq!(Paths(&diff::DiffReports(&old, &new)), [
+        "ScreenResolution.Monitors[\\\\.\\DISPLAY2].Width",
+        "Wsl.Distributions[Debian]",
+        "Users.Sessions[3]"
+    ]);
+}
+
+#[test]
+fn arrays_without_unique_identities_are_compared_by_index() {
+    let old = json!({ "GraphicsCard": { "Names": ["NVIDIA GeForce RTX 3070"] }, "Custom": [{ "Label": "Shell", "Value": "a" }, { "Label": "Shell", "Value": "b" }] });
+    let new = json!({ "GraphicsCard": { "Names": ["NVIDIA GeForce RTX 4070"] }, "Custom": [{ "Label": "Shell", "Value": "b" }, { "Label": "Shell", "Value": "b" }] });
+
+    assert_eq!(Paths(&diff::DiffReports(&old, &new)), ["GraphicsCard.Names[0]", "Custom[0].Value"]);
+}
+
+#[test]
+fn volatile_modules_are_left_out() {
+    let old = json!({
+        "Uptime": { "Uptime": { "secs": 60, "nanos": 0 } },
+        "ProcessorUsage": { "LoadPercentage": 10.0 },
+        "Processes": { "Count": 200 },
+        "Kernel": { "UBR": 4317 }
+    });
+    let new = json!({
+        "Uptime": { "Uptime": { "secs": 120, "nanos": 0 } },
+        "ProcessorUsage": { "LoadPercentage": 90.0 },
+        "Processes": { "Count": 201 },
+        "Kernel": { "UBR": 4391 }
+    });
+
+    assert_eq!(Paths(&diff::DiffReports(&old, &new)), ["Kernel.UBR"]);
+}
+
+#[test]
+fn free_space_changes_are_percentage_points_of_the_capacity() {
+    // from 50% to 70% full
+    let old = json!({ "Storage": { "Drives": [Drive("C:", 50, 100)] } });
+    let new = json!({ "Storage": { "Drives": [Drive("C:", 30, 100)] } });
+    let changes = diff::DiffReports(&old, &new);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].PointsChange, Some(20.0));
+    assert!(diff::RenderDiff(&changes)[2].contains("30 (+20.0 points)"));
+    assert!(diff::RenderDiffJson(&changes).contains("\"points\": 20.0"));
+}
+
+#[test]
+fn only_free_amounts_have_points() {
+    let old = json!({ "Memory": { "FreePhysicalMemory": 4, "TotalVisibleMemorySize": 16 }, "Kernel": { "UBR": 4317 } });
+    let new = json!({ "Memory": { "FreePhysicalMemory": 8, "TotalVisibleMemorySize": 16 }, "Kernel": { "UBR": 4391 } });
+    let changes = diff::DiffReports(&old, &new);
+
+    assert_eq!(Paths(&changes), ["Memory.FreePhysicalMemory", "Kernel.UBR"]);
+    assert_eq!(changes[0].PointsChange, Some(-25.0));
+    assert_eq!(changes[1].PointsChange, None);
+    assert!(!diff::RenderDiffJson(&changes[1..]).contains("points"));
+}
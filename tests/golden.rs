@@ -0,0 +1,122 @@
+#![allow(non_snake_case)]
+
+// renders the fixture system of `tests/fixtures` through the `winfetch` binary and compares the output with the files in
+// `tests/golden`; run with `WINFETCH_UPDATE_GOLDEN=1` to write the golden files instead
+use std::{
+    env,
+    fs,
+    path::{
+        Path,
+        PathBuf
+    },
+    process::Command
+};
+
+struct Case {
+    Name: &'static str,
+    Arguments: &'static [&'static str],
+    Columns: Option<usize>,
+    Language: &'static str,
+    // appended to the fixture config
+    Config: &'static str
+}
+
+const CASES: &[Case] = &[
+    Case { Name: "logo", Arguments: &[], Columns: Some(200), Language: "en", Config: "" },
+    Case { Name: "no_logo", Arguments: &["--no-logo"], Columns: Some(200), Language: "en", Config: "" },
+    Case { Name: "small_logo", Arguments: &["--logo", "small"], Columns: Some(200), Language: "en", Config: "" },
+    Case { Name: "no_terminal", Arguments: &["--no-logo"], Columns: None, Language: "en", Config: "" },
+    Case { Name: "stdout", Arguments: &["--stdout"], Columns: Some(60), Language: "en", Config: "" },
+    Case { Name: "json", Arguments: &["--json"], Columns: None, Language: "en", Config: "" },
+    Case { Name: "markdown", Arguments: &["--format", "markdown"], Columns: None, Language: "en", Config: "" },
+    Case { Name: "html", Arguments: &["--format", "html"], Columns: None, Language: "en", Config: "" },
+    Case { Name: "wrap_80", Arguments: &[], Columns: Some(80), Language: "en", Config: "" },
+    Case { Name: "wrap_40", Arguments: &["--no-logo"], Columns: Some(40), Language: "en", Config: "" },
+    Case { Name: "truncate_60", Arguments: &["--no-logo"], Columns: Some(60), Language: "en", Config: "[layout]\noverflow = \"truncate\"\n" },
+    Case { Name: "split_60", Arguments: &["--no-logo"], Columns: Some(60), Language: "en", Config: "[layout]\noverflow = \"split\"\n" },
+    Case { Name: "overflow_none", Arguments: &["--no-logo"], Columns: Some(40), Language: "en", Config: "[layout]\noverflow = \"none\"\n" },
+    Case {
+        Name: "templates",
+        Arguments: &["--no-logo", "--only", "memory,storage,resolution"],
+        Columns: Some(200),
+        Language: "en",
+        Config: "[templates]\nmemory = \"{used} of {total} ({percent})\"\nstorage = \"{drive} {fs} {free} free\"\nresolution = \"{count} monitors, {primary}\"\n"
+    },
+    Case { Name: "german", Arguments: &["--no-logo"], Columns: Some(60), Language: "de", Config: "" },
+    Case { Name: "japanese", Arguments: &["--no-logo"], Columns: Some(60), Language: "ja", Config: "" }
+];
+
+fn Fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(name)
+}
+
+fn Render(case: &Case) -> String {
+    // the language is a top-level key, so it goes before the tables of the fixture config
+    let config = format!("language = \"{}\"\n{}{}", case.Language, fs::read_to_string(Fixture("config.toml")).unwrap(), case.Config);
+
+    let config_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("golden-{}.toml", case.Name));
+    fs::write(&config_path, config).unwrap();
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_winfetch"));
+    command
+        .arg("--config")
+        .arg(&config_path)
+        .arg("--from-dump")
+        .arg(Fixture("system.json"))
+        .args(case.Arguments)
+        // the dates are rendered in the local time zone
+        .env("TZ", "UTC")
+        .env_remove("COLUMNS")
+        .env_remove("WINFETCH_CONFIG");
+
+    if let Some(columns) = case.Columns {
+        command.env("COLUMNS", columns.to_string());
+    }
+
+    let output = command.output().unwrap();
+    assert!(output.status.success(), "`{}` exited with {}", case.Name, output.status);
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// the escape codes are made visible so that the golden files can be read and diffed
+fn Visible(output: &str) -> String {
+    output.replace('\x1B', "\\e")
+}
+
+#[test]
+fn golden_renders() {
+    let update = env::var("WINFETCH_UPDATE_GOLDEN").is_ok_and(|value| value == "1");
+    let golden_directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden");
+    let mut mismatches = Vec::new();
+
+    for case in CASES {
+        let actual = Visible(&Render(case));
+        let path = golden_directory.join(format!("{}.txt", case.Name));
+
+        assert!(!actual.starts_with("winfetch:"), "`{}` failed: {}", case.Name, actual.trim_end());
+
+        if update {
+            fs::create_dir_all(&golden_directory).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+
+        let expected = fs::read_to_string(&path).unwrap_or_default().replace("\r\n", "\n");
+
+        if actual != expected {
+            let line = actual.lines().zip(expected.lines()).position(|(actual, expected)| actual != expected)
+                .unwrap_or_else(|| actual.lines().count().min(expected.lines().count()));
+
+            mismatches.push(format!(
+                "{}: first difference on line {}\n  expected: {}\n    actual: {}",
+                case.Name,
+                line + 1,
+                expected.lines().nth(line).unwrap_or("<end of file>"),
+                actual.lines().nth(line).unwrap_or("<end of file>")
+            ));
+        }
+    }
+
+    assert!(mismatches.is_empty(), "the output differs from the golden files; rerun with `WINFETCH_UPDATE_GOLDEN=1` if the change is intended\n\n{}", mismatches.join("\n\n"));
+}
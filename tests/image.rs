@@ -0,0 +1,93 @@
+#![allow(non_snake_case)]
+
+use linux_commands_on_windows::winfetch::{
+    config::Config,
+    image::{
+        kitty,
+        sixel,
+        EncodedImage,
+        Image,
+        ImageProtocol
+    }
+};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+const TRANSPARENT: [u8; 4] = [0, 0, 0, 0];
+
+// red and transparent on the first row, blue and red on the second
+fn TwoByTwo() -> Image {
+    Image { Width: 2, Height: 2, Pixels: vec![RED, TRANSPARENT, BLUE, RED] }
+}
+
+#[test]
+fn base64_matches_the_rfc_vectors() {
+    assert_eq!(kitty::EncodeBase64(b""), "");
+    assert_eq!(kitty::EncodeBase64(b"f"), "Zg==");
+    assert_eq!(kitty::EncodeBase64(b"fo"), "Zm8=");
+    assert_eq!(kitty::EncodeBase64(b"foo"), "Zm9v");
+    assert_eq!(kitty::EncodeBase64(b"foobar"), "Zm9vYmFy");
+}
+
+#[test]
+fn kitty_encodes_a_small_image_in_one_chunk() {
+    assert_eq!(
+        kitty::EncodeKitty(&TwoByTwo(), 1, 1),
+        "\x1B_Gf=32,s=2,v=2,a=T,c=1,r=1,C=1,q=2,m=0;/wAA/wAAAAAAAP///wAA/w==\x1B\\"
+    );
+}
+
+#[test]
+fn kitty_splits_the_payload_into_chunks() {
+    let image = Image { Width: 32, Height: 32, Pixels: vec![RED; 32 * 32] };
+    let payload = kitty::EncodeBase64(&RED.repeat(32 * 32));
+    let sequence = kitty::EncodeKitty(&image, 4, 2);
+
+    let first = format!("\x1B_Gf=32,s=32,v=32,a=T,c=4,r=2,C=1,q=2,m=1;{}\x1B\\", &payload[..4096]);
+    let second = format!("\x1B_Gm=0;{}\x1B\\", &payload[4096..]);
+
+    assert_eq!(payload.len(), 5464);
+    assert_eq!(sequence, first + &second);
+}
+
+#[test]
+fn sixel_quantizes_onto_the_color_cube() {
+    assert_eq!(sixel::QuantizeColor(RED), 180);
+    assert_eq!(sixel::QuantizeColor(BLUE), 5);
+    assert_eq!(sixel::QuantizeColor([255, 255, 255, 255]), 215);
+    assert_eq!(sixel::QuantizeColor([0, 0, 0, 255]), 0);
+}
+
+#[test]
+fn sixel_encodes_a_small_image_with_transparency() {
+    assert_eq!(
+        sixel::EncodeSixel(&TwoByTwo()),
+        "\x1BP0;1;0q\"1;1;2;2#5;2;0;0;100#180;2;100;0;0#5A$#180@A-\x1B\\"
+    );
+}
+
+#[test]
+fn sixel_compresses_runs() {
+    let image = Image { Width: 5, Height: 1, Pixels: vec![RED; 5] };
+
+    assert_eq!(sixel::EncodeSixel(&image), "\x1BP0;1;0q\"1;1;5;1#180;2;100;0;0#180!5@-\x1B\\");
+}
+
+#[test]
+fn images_are_sized_in_cells() {
+    let image = Image { Width: 4, Height: 6, Pixels: vec![RED; 4 * 6] };
+    let encoded = EncodedImage::EncodeImage(&image, ImageProtocol::Sixel, 2, 4, 4);
+
+    assert_eq!(encoded.Columns, 2);
+    assert_eq!(encoded.Rows, 3);
+    assert!(encoded.Sequence.starts_with("\x1BP0;1;0q\"1;1;8;12"));
+}
+
+#[test]
+fn zero_cell_sizes_are_rejected() {
+    for key in ["columns", "cell_width", "cell_height"] {
+        assert!(Config::ParseConfig(&format!("[image]\n{} = 0", key)).is_err(), "`{}` = 0 was accepted", key);
+    }
+
+    assert!(Config::ParseConfig("[image]\ncolumns = 16\ncell_width = 8\ncell_height = 16").is_ok());
+}
@@ -0,0 +1,85 @@
+#![allow(non_snake_case)]
+
+use linux_commands_on_windows::winfetch::model::locale::{
+    self,
+    TimeZone
+};
+
+// the keys are written in the format of `--dump`, since the registry types are internal
+fn FromRegistryKey(key: &str) -> Option<TimeZone> {
+    TimeZone::FromRegistryKey(&serde_json::from_str(key).unwrap())
+}
+
+fn PreloadedLayouts(key: &str) -> Vec<String> {
+    locale::PreloadedLayouts(&serde_json::from_str(key).unwrap())
+}
+
+#[test]
+fn the_time_zone_follows_the_active_bias() {
+    // the biases are unsigned in the registry: -60 and -120 minutes
+    let summer = FromRegistryKey(r#"{
+        "Values": {
+            "TimeZoneKeyName": { "String": "W. Europe Standard Time\u0000" },
+            "Bias": { "Dword": 4294967236 },
+            "ActiveTimeBias": { "Dword": 4294967176 }
+        },
+        "SubKeys": {}
+    }"#).unwrap();
+
+    assert_eq!(summer, TimeZone { Name: String::from("W. Europe Standard Time"), ActiveBias: -120, DaylightSaving: true });
+    assert_eq!(summer.UtcOffset(), "UTC+02:00");
+
+    let winter = FromRegistryKey(r#"{
+        "Values": {
+            "TimeZoneKeyName": { "String": "Eastern Standard Time" },
+            "Bias": { "Dword": 300 }
+        },
+        "SubKeys": {}
+    }"#).unwrap();
+
+    assert_eq!(winter, TimeZone { Name: String::from("Eastern Standard Time"), ActiveBias: 300, DaylightSaving: false });
+    assert_eq!(winter.UtcOffset(), "UTC-05:00");
+}
+
+#[test]
+fn time_zones_without_a_name_or_bias_are_none() {
+    assert_eq!(FromRegistryKey(r#"{ "Values": { "Bias": { "Dword": 0 } }, "SubKeys": {} }"#), None);
+    assert_eq!(FromRegistryKey(r#"{ "Values": { "TimeZoneKeyName": { "String": "UTC" } }, "SubKeys": {} }"#), None);
+}
+
+#[test]
+fn utc_offsets_are_formatted_with_hours_and_minutes() {
+    assert_eq!(locale::FormatUtcOffset(0), "UTC+00:00");
+    assert_eq!(locale::FormatUtcOffset(330), "UTC+05:30");
+    assert_eq!(locale::FormatUtcOffset(-210), "UTC-03:30");
+    assert_eq!(locale::FormatUtcOffset(765), "UTC+12:45");
+}
+
+#[test]
+fn preloaded_layouts_are_ordered_and_substituted() {
+    let layouts = PreloadedLayouts(r#"{
+        "Values": {},
+        "SubKeys": {
+            "Preload": {
+                "Values": {
+                    "1": { "String": "00000409" },
+                    "10": { "String": "00000411" },
+                    "2": { "String": "00000407" },
+                    "name": { "String": "ignored" }
+                },
+                "SubKeys": {}
+            },
+            "Substitutes": {
+                "Values": { "00000407": { "String": "00010407" } },
+                "SubKeys": {}
+            }
+        }
+    }"#);
+
+    assert_eq!(layouts, ["00000409", "00010407", "00000411"]);
+}
+
+#[test]
+fn keys_without_preload_have_no_layouts() {
+    assert!(PreloadedLayouts(r#"{ "Values": {}, "SubKeys": {} }"#).is_empty());
+}
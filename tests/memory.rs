@@ -0,0 +1,18 @@
+use linux_commands_on_windows::winfetch::model::memory::Memory;
+
+const GIGABYTE: u64 = 1024 * 1024 * 1024;
+
+#[test]
+fn used_memory_is_total_minus_free() {
+    let memory = Memory { FreePhysicalMemory: 4 * GIGABYTE, TotalVisibleMemorySize: 16 * GIGABYTE };
+
+    assert!(memory.to_string().ends_with("12.00 GB / 16.00 GB"), "{}", memory);
+}
+
+#[test]
+fn free_memory_above_the_total_does_not_underflow() {
+    // both sizes come from one row, but a broken provider or an edited dump can still report them this way
+    let memory = Memory { FreePhysicalMemory: 17 * GIGABYTE, TotalVisibleMemorySize: 16 * GIGABYTE };
+
+    assert!(memory.to_string().ends_with("0.00 GB / 16.00 GB"), "{}", memory);
+}
@@ -0,0 +1,33 @@
+use linux_commands_on_windows::winfetch::model::names::{
+    Membership,
+    NameCase
+};
+
+#[test]
+fn domain_joined_computers_report_their_domain() {
+    assert_eq!(
+        Membership::FromComputerSystem(true, Some("corp.contoso.com"), None),
+        Some(Membership::Domain(String::from("corp.contoso.com")))
+    );
+    // the workgroup is ignored when the computer is part of a domain
+    assert_eq!(
+        Membership::FromComputerSystem(true, Some("corp.contoso.com"), Some("WORKGROUP")),
+        Some(Membership::Domain(String::from("corp.contoso.com")))
+    );
+    assert_eq!(Membership::FromComputerSystem(true, None, Some("WORKGROUP")), None);
+}
+
+#[test]
+fn workgroup_computers_report_their_workgroup() {
+    assert_eq!(Membership::FromComputerSystem(false, Some("WORKGROUP"), Some("HOME")), Some(Membership::Workgroup(String::from("HOME"))));
+    // `Domain` holds the workgroup name when `Workgroup` is not set
+    assert_eq!(Membership::FromComputerSystem(false, Some("WORKGROUP"), None), Some(Membership::Workgroup(String::from("WORKGROUP"))));
+    assert_eq!(Membership::FromComputerSystem(false, None, None), None);
+}
+
+#[test]
+fn names_are_lowercased_unless_preserved() {
+    assert_eq!(NameCase::default(), NameCase::Lower);
+    assert_eq!(NameCase::Lower.Apply("CONTOSO\\Jane"), "contoso\\jane");
+    assert_eq!(NameCase::Preserve.Apply("CONTOSO\\Jane"), "CONTOSO\\Jane");
+}
@@ -0,0 +1,37 @@
+use linux_commands_on_windows::winfetch::model::os::{
+    self,
+    ActivationStatus
+};
+
+#[test]
+fn windows_11_is_named_from_build_22000() {
+    assert_eq!(os::CorrectProductName("Windows 10 Pro", 19045), "Windows 10 Pro");
+    assert_eq!(os::CorrectProductName("Windows 10 Pro", 21999), "Windows 10 Pro");
+    assert_eq!(os::CorrectProductName("Windows 10 Pro", 22000), "Windows 11 Pro");
+    assert_eq!(os::CorrectProductName("Windows 10 Pro", 26100), "Windows 11 Pro");
+}
+
+#[test]
+fn the_edition_is_kept() {
+    for edition in ["Home", "Pro", "Pro for Workstations", "Education", "Enterprise", "Enterprise LTSC 2024", "IoT Enterprise"] {
+        assert_eq!(os::CorrectProductName(&format!("Windows 10 {}", edition), 22631), format!("Windows 11 {}", edition));
+    }
+
+    assert_eq!(os::CorrectProductName("Windows 10 Enterprise LTSC 2021", 19044), "Windows 10 Enterprise LTSC 2021");
+}
+
+#[test]
+fn other_product_names_are_left_alone() {
+    assert_eq!(os::CorrectProductName("Windows Server 2022 Datacenter", 20348), "Windows Server 2022 Datacenter");
+    assert_eq!(os::CorrectProductName("Windows Server 2025 Standard", 26100), "Windows Server 2025 Standard");
+    assert_eq!(os::CorrectProductName("Windows 11 Pro", 22631), "Windows 11 Pro");
+    // an unparseable build is passed as 0
+    assert_eq!(os::CorrectProductName("Windows 10 Pro", 0), "Windows 10 Pro");
+}
+
+#[test]
+fn license_statuses_are_mapped() {
+    assert_eq!(ActivationStatus::FromLicenseStatus(1).unwrap(), ActivationStatus::Licensed);
+    assert_eq!(ActivationStatus::FromLicenseStatus(6).unwrap(), ActivationStatus::ExtendedGrace);
+    assert!(ActivationStatus::FromLicenseStatus(7).is_err());
+}
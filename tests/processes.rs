@@ -0,0 +1,72 @@
+#![allow(non_snake_case)]
+
+use std::time::Duration;
+
+use linux_commands_on_windows::winfetch::model::processes::{
+    self,
+    ProcessInfo,
+    ProcessSort
+};
+
+fn Process(process_id: u32, name: &str, threads: u32, working_set: u64, cpu_seconds: u64) -> ProcessInfo {
+    ProcessInfo {
+        ProcessId: process_id,
+        Name: name.to_string(),
+        Threads: threads,
+        WorkingSet: working_set,
+        CpuTime: Duration::from_secs(cpu_seconds)
+    }
+}
+
+fn Processes() -> Vec<ProcessInfo> {
+    vec![
+        Process(0, "System Idle Process", 16, 8192, 90000),
+        Process(4, "System", 180, 0, 5400),
+        Process(8844, "firefox.exe", 96, 1 << 30, 3725),
+        Process(9120, "Code.exe", 31, 1 << 29, 42),
+        Process(10244, "explorer.exe", 77, 1 << 29, 128)
+    ]
+}
+
+fn Names(processes: &[ProcessInfo]) -> Vec<&str> {
+    processes.iter().map(|process| process.Name.as_str()).collect()
+}
+
+#[test]
+fn threads_are_summed_over_every_process() {
+    assert_eq!(processes::TotalThreads(&Processes()), 400);
+    assert_eq!(processes::TotalThreads(&[]), 0);
+}
+
+#[test]
+fn processes_are_sorted_by_cpu_time() {
+    assert_eq!(Names(&processes::TopProcesses(&Processes(), ProcessSort::Cpu, 3)), ["System", "firefox.exe", "explorer.exe"]);
+}
+
+#[test]
+fn processes_are_sorted_by_working_set() {
+    // the tie between the two 512 MB processes is broken by name
+    assert_eq!(Names(&processes::TopProcesses(&Processes(), ProcessSort::Memory, 3)), ["firefox.exe", "Code.exe", "explorer.exe"]);
+}
+
+#[test]
+fn ties_are_broken_by_name() {
+    let tied = vec![Process(3, "c.exe", 1, 0, 10), Process(1, "a.exe", 1, 0, 10), Process(2, "b.exe", 1, 0, 10)];
+
+    assert_eq!(Names(&processes::TopProcesses(&tied, ProcessSort::Cpu, 3)), ["a.exe", "b.exe", "c.exe"]);
+    assert_eq!(Names(&processes::TopProcesses(&tied, ProcessSort::Memory, 3)), ["a.exe", "b.exe", "c.exe"]);
+}
+
+#[test]
+fn the_idle_process_is_left_out() {
+    let top = processes::TopProcesses(&Processes(), ProcessSort::Cpu, usize::MAX);
+
+    assert!(top.iter().all(|process| process.ProcessId != 0));
+}
+
+#[test]
+fn counts_larger_than_the_list_return_every_process() {
+    assert_eq!(processes::TopProcesses(&Processes(), ProcessSort::Cpu, 10).len(), 4);
+    assert!(processes::TopProcesses(&Processes(), ProcessSort::Cpu, 0).is_empty());
+    assert!(processes::TopProcesses(&[], ProcessSort::Memory, 3).is_empty());
+}
@@ -0,0 +1,85 @@
+#![allow(non_snake_case)]
+
+use linux_commands_on_windows::winfetch::model::processorusage::{
+    self,
+    ProcessorCounter,
+    ProcessorLoad,
+    ProcessorTimes
+};
+
+fn Counter(name: &str, idle_time: u64, timestamp: u64) -> ProcessorCounter {
+    ProcessorCounter { Name: name.to_string(), IdleTime: idle_time, Timestamp: timestamp }
+}
+
+fn Names(loads: &[ProcessorLoad]) -> Vec<&str> {
+    loads.iter().map(|load| load.Name.as_str()).collect()
+}
+
+#[test]
+fn load_is_the_share_of_time_not_idle() {
+    let before = ProcessorTimes { Idle: 1000, Kernel: 2000, User: 1000 };
+    let after = ProcessorTimes { Idle: 1750, Kernel: 3000, User: 1500 };
+
+    // 1500 of which 750 idle
+    assert_eq!(processorusage::LoadBetween(&before, &after), 50.0);
+}
+
+#[test]
+fn no_elapsed_time_is_no_load() {
+    let times = ProcessorTimes { Idle: 1000, Kernel: 2000, User: 1000 };
+
+    assert_eq!(processorusage::LoadBetween(&times, &times), 0.0);
+    assert_eq!(processorusage::CounterLoadsBetween(&[Counter("0,0", 100, 500)], &[Counter("0,0", 100, 500)]).0[0].LoadPercentage, 0.0);
+}
+
+#[test]
+fn counters_going_backwards_saturate() {
+    let before = ProcessorTimes { Idle: 5000, Kernel: 9000, User: 1000 };
+    let after = ProcessorTimes { Idle: 1000, Kernel: 2000, User: 500 };
+
+    assert_eq!(processorusage::LoadBetween(&before, &after), 0.0);
+
+    // the idle time cannot exceed the elapsed time, and a timestamp going backwards is no time at all
+    let (cores, _) = processorusage::CounterLoadsBetween(
+        &[Counter("0,0", 100, 1000), Counter("0,1", 0, 1000)],
+        &[Counter("0,0", 900, 1500), Counter("0,1", 100, 500)]
+    );
+
+    assert_eq!(cores[0].LoadPercentage, 0.0);
+    assert_eq!(cores[1].LoadPercentage, 0.0);
+    assert!(cores.iter().all(|core| core.LoadPercentage.is_finite()));
+}
+
+#[test]
+fn sockets_are_split_from_cores() {
+    let before = [Counter("0,0", 0, 0), Counter("0,_Total", 0, 0), Counter("1,0", 0, 0), Counter("1,_Total", 0, 0), Counter("_Total", 0, 0)];
+    let after = [Counter("0,0", 250, 1000), Counter("0,_Total", 500, 1000), Counter("1,0", 1000, 1000), Counter("1,_Total", 750, 1000), Counter("_Total", 0, 1000)];
+    let (cores, sockets) = processorusage::CounterLoadsBetween(&before, &after);
+
+    assert_eq!(Names(&cores), ["0,0", "1,0"]);
+    assert_eq!(cores[0].LoadPercentage, 75.0);
+    assert_eq!(cores[1].LoadPercentage, 0.0);
+
+    // the machine-wide `_Total` is left out
+    assert_eq!(Names(&sockets), ["0", "1"]);
+    assert_eq!(sockets[0].LoadPercentage, 50.0);
+    assert_eq!(sockets[1].LoadPercentage, 25.0);
+}
+
+#[test]
+fn cores_are_sorted_numerically() {
+    let names = ["0,10", "0,9", "1,0", "0,1", "0,0", "0,_Total", "1,_Total"];
+    let before = names.map(|name| Counter(name, 0, 0));
+    let after = names.map(|name| Counter(name, 0, 100));
+    let (cores, sockets) = processorusage::CounterLoadsBetween(&before, &after);
+
+    assert_eq!(Names(&cores), ["0,0", "0,1", "0,9", "0,10", "1,0"]);
+    assert_eq!(Names(&sockets), ["0", "1"]);
+}
+
+#[test]
+fn instances_missing_from_the_first_sample_are_left_out() {
+    let (cores, _) = processorusage::CounterLoadsBetween(&[Counter("0,0", 0, 0)], &[Counter("0,0", 0, 100), Counter("0,1", 0, 100)]);
+
+    assert_eq!(Names(&cores), ["0,0"]);
+}
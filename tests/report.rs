@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use linux_commands_on_windows::winfetch::{
+    dump::{
+        self,
+        RawDump,
+        RecordKey
+    },
+    module::Module,
+    report::{
+        self,
+        Options
+    }
+};
+
+#[test]
+fn a_failing_module_is_left_out_of_the_report() {
+    let mut dump = RawDump::LoadDump(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join("system.json")).unwrap();
+
+    for record in &mut dump.Records {
+        if let RecordKey::Wmi { Query, .. } = &record.Key {
+            if Query.ends_with("FROM Win32_BIOS") && Query.contains("SMBIOSBIOSVersion") {
+                record.Value = None;
+                record.Error = Some(String::from("wmi query failed"));
+            }
+        }
+    }
+
+    dump::StartReplaying(dump);
+
+    let options = Options { Modules: vec![Module::Bios, Module::Memory], ..Options::default() };
+    let mut report = report::Collect(&options).unwrap();
+
+    assert!(report.Bios.is_none());
+    assert!(report.Memory.is_some());
+    assert_eq!(report.Errors.get(&Module::Bios).map(String::as_str), Some("wmi query failed"));
+    assert!(!report.Errors.contains_key(&Module::Memory));
+
+    // a refresh keeps what was collected before
+    report::Refresh(&mut report, &options).unwrap();
+
+    assert!(report.Memory.is_some());
+    assert!(report.Errors.contains_key(&Module::Bios));
+}
@@ -0,0 +1,44 @@
+#![allow(non_snake_case)]
+
+use linux_commands_on_windows::winfetch::model::screenres;
+
+// the values are written in the format of `--dump`, since the wmi types are internal
+fn DecodeFriendlyName(values: &str) -> Option<String> {
+    let values: Vec<_> = serde_json::from_str(values).unwrap();
+
+    screenres::DecodeFriendlyName(&values)
+}
+
+#[test]
+fn friendly_names_stop_at_the_padding() {
+    assert_eq!(DecodeFriendlyName("[68,69,76,76,32,85,50,55,49,57,68,0,0,0]").as_deref(), Some("DELL U2719D"));
+    assert_eq!(DecodeFriendlyName("[76,71,0,85,76,84,82,65]").as_deref(), Some("LG"));
+    assert_eq!(DecodeFriendlyName("[32,80,76,50,55,57,50,81,32,0]").as_deref(), Some("PL2792Q"));
+}
+
+#[test]
+fn empty_friendly_names_are_none() {
+    assert_eq!(DecodeFriendlyName("[]"), None);
+    assert_eq!(DecodeFriendlyName("[0,0,0,0]"), None);
+    assert_eq!(DecodeFriendlyName("[32,32,0]"), None);
+}
+
+#[test]
+fn instance_names_match_their_interface_path() {
+    let device_id = r"\\?\DISPLAY#GSM5B7F#4&2f2f6c4a&0&UID4352#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}";
+
+    assert!(screenres::MatchesInstanceName(device_id, r"DISPLAY\GSM5B7F\4&2f2f6c4a&0&UID4352_0"));
+    assert!(screenres::MatchesInstanceName(device_id, r"display\gsm5b7f\4&2F2F6C4A&0&uid4352_0"));
+    assert!(screenres::MatchesInstanceName(device_id, r"DISPLAY\GSM5B7F\4&2f2f6c4a&0&UID4352"));
+}
+
+#[test]
+fn other_monitors_do_not_match() {
+    let device_id = r"\\?\DISPLAY#GSM5B7F#4&2f2f6c4a&0&UID4352#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}";
+
+    assert!(!screenres::MatchesInstanceName(device_id, r"DISPLAY\DEL41A3\4&2f2f6c4a&0&UID4353_0"));
+    // a prefix of the instance id is not enough
+    assert!(!screenres::MatchesInstanceName(device_id, r"DISPLAY\GSM5B7F\4&2f2f6c4a&0&UID435_0"));
+    assert!(!screenres::MatchesInstanceName(device_id, "_0"));
+    assert!(!screenres::MatchesInstanceName(device_id, ""));
+}
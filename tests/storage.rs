@@ -0,0 +1,74 @@
+#![allow(non_snake_case)]
+
+use linux_commands_on_windows::winfetch::{
+    config::Config,
+    model::storage::{
+        self,
+        DriveType,
+        StorageDrive,
+        StorageFilter
+    }
+};
+
+const GIBIBYTE: u64 = 1024 * 1024 * 1024;
+
+fn Drive(drive_type: DriveType, size: u64) -> StorageDrive {
+    StorageDrive {
+        DriveLetter: String::from("C:"),
+        DriveType: drive_type,
+        FileSystem: Some(String::from("NTFS")),
+        VolumeName: None,
+        FreeSpace: size / 2,
+        Size: size,
+        Disk: None
+    }
+}
+
+#[test]
+fn drives_without_a_size_are_never_allowed() {
+    assert!(!StorageFilter::default().Allows(&Drive(DriveType::Optical, 0)));
+    assert!(StorageFilter::default().Allows(&Drive(DriveType::Optical, 1)));
+}
+
+#[test]
+fn drives_are_filtered_by_type_and_size() {
+    let filter = StorageFilter { DriveTypes: vec![DriveType::Fixed, DriveType::Removable], MinimumSize: GIBIBYTE };
+
+    assert!(filter.Allows(&Drive(DriveType::Fixed, 512 * GIBIBYTE)));
+    assert!(filter.Allows(&Drive(DriveType::Removable, GIBIBYTE)));
+    assert!(!filter.Allows(&Drive(DriveType::Removable, GIBIBYTE - 1)));
+    assert!(!filter.Allows(&Drive(DriveType::Network, 512 * GIBIBYTE)));
+}
+
+#[test]
+fn the_minimum_size_is_in_gibibytes() {
+    let config = Config::ParseConfig("[storage]\nmin_size = 1.5\n").unwrap();
+
+    assert_eq!(config.Storage.Filter().MinimumSize, 3 * GIBIBYTE / 2);
+    // negative sizes allow every drive
+    assert_eq!(Config::ParseConfig("[storage]\nmin_size = -1.0\n").unwrap().Storage.Filter().MinimumSize, 0);
+}
+
+#[test]
+fn object_path_keys_are_unescaped() {
+    assert_eq!(storage::ParseObjectPathKey(r#"\\HOST\root\cimv2:Win32_LogicalDisk.DeviceID="C:""#).as_deref(), Some("C:"));
+    assert_eq!(
+        storage::ParseObjectPathKey(r#"\\HOST\root\cimv2:Win32_DiskPartition.DeviceID="Disk #0, Partition #1""#).as_deref(),
+        Some("Disk #0, Partition #1")
+    );
+    assert_eq!(
+        storage::ParseObjectPathKey(r#"\\HOST\root\cimv2:Win32_DiskDrive.DeviceID="\\\\.\\PHYSICALDRIVE0""#).as_deref(),
+        Some(r"\\.\PHYSICALDRIVE0")
+    );
+    assert_eq!(storage::ParseObjectPathKey(r"\\HOST\root\cimv2:Win32_LogicalDisk"), None);
+    assert_eq!(storage::ParseObjectPathKey(r#"Win32_LogicalDisk.DeviceID="C:"#), None);
+}
+
+#[test]
+fn disk_indexes_are_read_from_partition_names() {
+    assert_eq!(storage::ParseDiskIndex("Disk #0, Partition #1"), Some(0));
+    assert_eq!(storage::ParseDiskIndex("Disk #12, Partition #0"), Some(12));
+    assert_eq!(storage::ParseDiskIndex("Disk #3"), Some(3));
+    assert_eq!(storage::ParseDiskIndex("Partition #1"), None);
+    assert_eq!(storage::ParseDiskIndex("Disk #, Partition #1"), None);
+}
@@ -0,0 +1,23 @@
+use linux_commands_on_windows::winfetch::model::temperature;
+
+#[test]
+fn tenths_of_kelvin_are_converted_to_celsius() {
+    assert!((temperature::TenthsOfKelvinToCelsius(2732) - 0.05).abs() < 1e-9);
+    assert!((temperature::TenthsOfKelvinToCelsius(3132) - 40.05).abs() < 1e-9);
+    assert!((temperature::TenthsOfKelvinToCelsius(0) + 273.15).abs() < 1e-9);
+}
+
+#[test]
+fn zone_names_are_shortened() {
+    assert_eq!(temperature::ZoneName(r"ACPI\ThermalZone\TZ00_0"), "TZ00");
+    assert_eq!(temperature::ZoneName(r"\_TZ.TZ00"), "TZ00");
+    assert_eq!(temperature::ZoneName(r"\_TZ.THRM"), "THRM");
+    assert_eq!(temperature::ZoneName("CPUZ"), "CPUZ");
+}
+
+#[test]
+fn only_a_numeric_suffix_is_removed() {
+    assert_eq!(temperature::ZoneName(r"ACPI\ThermalZone\CPU_ZONE"), "CPU_ZONE");
+    assert_eq!(temperature::ZoneName(r"ACPI\ThermalZone\_0"), "_0");
+    assert_eq!(temperature::ZoneName(r"ACPI\ThermalZone\TZ01_"), "TZ01_");
+}
@@ -0,0 +1,103 @@
+#![allow(non_snake_case)]
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use linux_commands_on_windows::winfetch::{
+    template::{
+        PlaceholderKind,
+        Template,
+        TemplateValue
+    },
+    utils
+};
+
+const PLACEHOLDERS: &[(&str, PlaceholderKind)] = &[
+    ("name", PlaceholderKind::Text),
+    ("count", PlaceholderKind::Integer),
+    ("used", PlaceholderKind::Bytes),
+    ("percent", PlaceholderKind::Percent),
+    ("date", PlaceholderKind::DateTime)
+];
+
+fn Values() -> BTreeMap<&'static str, TemplateValue> {
+    BTreeMap::from([
+        ("name", TemplateValue::Text(String::from("Ubuntu"))),
+        ("count", TemplateValue::Integer(3)),
+        ("used", TemplateValue::Bytes(1_610_612_736)),
+        ("percent", TemplateValue::Percent(37.6)),
+        ("date", TemplateValue::DateTime(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap().and_hms_opt(9, 30, 0).unwrap()))
+    ])
+}
+
+fn Render(template: &str) -> String {
+    let template = Template::Parse(template).unwrap();
+    template.Validate(PLACEHOLDERS).unwrap();
+
+    template.Render(&Values()).unwrap()
+}
+
+#[test]
+fn doubled_braces_are_literal() {
+    assert_eq!(Render("{{{name}}} {{}}"), "{Ubuntu} {}");
+    assert_eq!(Render("no placeholders"), "no placeholders");
+}
+
+#[test]
+fn malformed_templates_are_rejected() {
+    assert!(Template::Parse("{name").is_err());
+    assert!(Template::Parse("{na{me}").is_err());
+    assert!(Template::Parse("name}").is_err());
+    assert!(Template::Parse("{}").is_err());
+    assert!(Template::Parse("{ :upper}").is_err());
+}
+
+#[test]
+fn unknown_placeholders_are_rejected() {
+    let error = Template::Parse("{nmae}").unwrap().Validate(PLACEHOLDERS).unwrap_err();
+
+    assert!(error.0.contains("unknown placeholder `nmae`"), "{}", error.0);
+    assert!(error.0.contains("name, count, used, percent, date"), "{}", error.0);
+    assert!(Template::Parse("{name}").unwrap().Validate(&[]).is_err());
+}
+
+#[test]
+fn invalid_formats_are_rejected() {
+    for template in ["{name:title}", "{count:03}", "{used:pb}", "{percent:pie}", "{date:%Q}", "{date:%Y %z}", "{date:%:z}", "{date:%Z}"] {
+        assert!(Template::Parse(template).unwrap().Validate(PLACEHOLDERS).is_err(), "`{}` was accepted", template);
+    }
+}
+
+#[test]
+fn text_and_integers_are_rendered() {
+    assert_eq!(Render("{name:lower} {name:upper} {name} x{count}"), "ubuntu UBUNTU Ubuntu x3");
+}
+
+#[test]
+fn bytes_are_rendered_in_the_requested_unit() {
+    assert_eq!(Render("{used:b}"), "1610612736");
+    assert_eq!(Render("{used:kb}"), "1610612.74");
+    assert_eq!(Render("{used:gb}"), "1.61");
+    assert_eq!(Render("{used:mib}"), "1536.00");
+    assert_eq!(Render("{used:gib}"), "1.50");
+}
+
+#[test]
+fn percents_are_rounded_or_drawn_as_bars() {
+    assert_eq!(Render("{percent}%"), "38%");
+    assert_eq!(utils::StripANSIEscapeSequences(&Render("{percent:bar}")), "[ ■■■------- ]");
+}
+
+#[test]
+fn dates_are_rendered_with_strftime_formats() {
+    assert_eq!(Render("{date}"), "2024-03-15 09:30");
+    assert_eq!(Render("{date:%d/%m/%Y}"), "15/03/2024");
+}
+
+#[test]
+fn placeholders_without_a_value_are_empty() {
+    let template = Template::Parse("[{name}]").unwrap();
+
+    assert_eq!(template.Render(&BTreeMap::new()).unwrap(), "[]");
+}
@@ -0,0 +1,25 @@
+use chrono::{
+    TimeZone,
+    Utc
+};
+
+use linux_commands_on_windows::winfetch::model::users;
+
+// the ticks between 1601-01-01 and 1970-01-01
+const UNIX_EPOCH_TICKS: i64 = 116_444_736_000_000_000;
+
+#[test]
+fn file_times_are_converted_from_1601() {
+    assert_eq!(users::FileTimeToDateTime(UNIX_EPOCH_TICKS).unwrap(), Utc.timestamp_opt(0, 0).unwrap());
+    // 2024-03-01 08:30:15.5 utc
+    assert_eq!(
+        users::FileTimeToDateTime(133_537_554_155_000_000).unwrap(),
+        Utc.with_ymd_and_hms(2024, 3, 1, 8, 30, 15).unwrap() + chrono::Duration::milliseconds(500)
+    );
+}
+
+#[test]
+fn unknown_file_times_are_none() {
+    assert_eq!(users::FileTimeToDateTime(0), None);
+    assert_eq!(users::FileTimeToDateTime(-1), None);
+}
@@ -0,0 +1,98 @@
+use linux_commands_on_windows::winfetch::utils;
+
+#[test]
+fn html_is_escaped() {
+    assert_eq!(utils::EscapeHTML(r#"<a href="x">&</a>"#), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+    assert_eq!(utils::ANSIToHTML("\x1B[32m<b>&\x1B[0m \"x\""), "<span style=\"color:#0dbc79\">&lt;b&gt;&amp;</span> &quot;x&quot;");
+}
+
+#[test]
+fn colors_and_bold_become_spans() {
+    assert_eq!(utils::ANSIToHTML("plain"), "plain");
+    assert_eq!(utils::ANSIToHTML("\x1B[34mblue\x1B[0m"), "<span style=\"color:#2472c8\">blue</span>");
+    assert_eq!(utils::ANSIToHTML("\x1B[1;91mbold\x1B[22mred\x1B[m"), "<span style=\"color:#f14c4c;font-weight:bold\">bold</span><span style=\"color:#f14c4c\">red</span>");
+    assert_eq!(utils::ANSIToHTML("\x1B[1;34mbold\x1B[39mdefault\x1B[0m"), "<span style=\"color:#2472c8;font-weight:bold\">bold</span><span style=\"font-weight:bold\">default</span>");
+}
+
+#[test]
+fn every_span_is_closed() {
+    // a new sequence closes the span before it, and a missing reset is closed at the end
+    assert_eq!(
+        utils::ANSIToHTML("\x1B[31ma\x1B[32mb\x1B[1mc"),
+        "<span style=\"color:#cd3131\">a</span><span style=\"color:#0dbc79\">b</span><span style=\"color:#0dbc79;font-weight:bold\">c</span>"
+    );
+}
+
+#[test]
+fn extended_colors_are_converted() {
+    assert_eq!(utils::ANSIToHTML("\x1B[38;5;196mx\x1B[0m"), "<span style=\"color:#ff0000\">x</span>");
+    assert_eq!(utils::ANSIToHTML("\x1B[38;5;9mx\x1B[0m"), "<span style=\"color:#f14c4c\">x</span>");
+    assert_eq!(utils::ANSIToHTML("\x1B[38;5;244mx\x1B[0m"), "<span style=\"color:#808080\">x</span>");
+    assert_eq!(utils::ANSIToHTML("\x1B[38;2;255;128;0mx\x1B[0m"), "<span style=\"color:#ff8000\">x</span>");
+    // the zeroes of a truecolor sequence are not resets
+    assert_eq!(utils::ANSIToHTML("\x1B[1;38;2;0;0;0mx\x1B[0m"), "<span style=\"color:#000000;font-weight:bold\">x</span>");
+}
+
+#[test]
+fn backgrounds_and_other_sequences_are_dropped() {
+    assert_eq!(utils::ANSIToHTML("\x1B[48;2;0;0;0;32mx\x1B[0m"), "<span style=\"color:#0dbc79\">x</span>");
+    assert_eq!(utils::ANSIToHTML("\x1B[2Ka\x1B[1Ab"), "ab");
+}
+
+#[test]
+fn wide_characters_take_two_columns() {
+    assert_eq!(utils::VisibleWidth("abc"), 3);
+    assert_eq!(utils::VisibleWidth("日本語"), 6);
+    assert_eq!(utils::VisibleWidth("한국어"), 6);
+    assert_eq!(utils::VisibleWidth("ｆｕｌｌ"), 8);
+    assert_eq!(utils::VisibleWidth("🌍😀🚀🦀🫠"), 10);
+    // combining marks take no column
+    assert_eq!(utils::VisibleWidth("e\u{301}"), 1);
+}
+
+#[test]
+fn escape_sequences_take_no_columns() {
+    assert_eq!(utils::VisibleWidth("\x1B[1;34mOS\x1B[0m: \x1B[38;5;196mWindows\x1B[m"), 11);
+    assert_eq!(utils::VisibleWidth("\x1B[32m日本\x1B[0m"), 4);
+}
+
+#[test]
+fn lines_are_split_at_visible_columns() {
+    assert_eq!(utils::SplitAtVisibleWidth("\x1B[32mabcdef\x1B[0m", 3), ("\x1B[32mabc", "def\x1B[0m"));
+    // a wide character that does not fit goes to the second part
+    assert_eq!(utils::SplitAtVisibleWidth("ab日本", 3), ("ab", "日本"));
+    assert_eq!(utils::SplitAtVisibleWidth("abc", 5), ("abc", ""));
+}
+
+#[test]
+fn truncated_lines_end_with_an_ellipsis() {
+    assert_eq!(utils::TruncateVisible("short", 10), "short");
+    assert_eq!(utils::TruncateVisible("abcdefghij", 5), "abcd…");
+    assert_eq!(utils::TruncateVisible("日本語のテキスト", 7), "日本語…");
+    assert_eq!(utils::TruncateVisible("🚀🚀🚀🚀", 6), "🚀🚀…");
+}
+
+#[test]
+fn truncation_does_not_split_escape_sequences() {
+    let truncated = utils::TruncateVisible("\x1B[34mLabel\x1B[0m: \x1B[91mvalue\x1B[0m", 9);
+
+    assert_eq!(truncated, "\x1B[34mLabel\x1B[0m: \x1B[91mv\x1B[0m…");
+    assert_eq!(utils::VisibleWidth(&truncated), 9);
+    assert_eq!(utils::StripANSIEscapeSequences(&truncated), "Label: v…");
+}
+
+#[test]
+fn lines_wrap_after_spaces() {
+    assert_eq!(utils::WrapVisible("one two three four", 9), ["one two", "three", "four"]);
+    assert_eq!(utils::WrapVisible("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+    assert_eq!(utils::WrapVisible("日本語のテキスト", 6), ["日本語", "のテキ", "スト"]);
+    assert_eq!(utils::WrapVisible("\x1B[32mgreen text\x1B[0m", 6), ["\x1B[32mgreen", "text\x1B[0m"]);
+    assert_eq!(utils::WrapVisible("fits", 10), ["fits"]);
+}
+
+#[test]
+fn percentages_out_of_range_are_clamped() {
+    assert_eq!(utils::GeneratePercentageBar(-5), utils::GeneratePercentageBar(0));
+    assert_eq!(utils::GeneratePercentageBar(250), utils::GeneratePercentageBar(100));
+    assert_eq!(utils::GeneratePercentageBar(0), "[ ---------- ]");
+}
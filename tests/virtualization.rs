@@ -0,0 +1,52 @@
+use linux_commands_on_windows::winfetch::model::virtualization::{
+    self,
+    Hypervisor
+};
+
+// the manufacturer, model, bios version and asset tag, and the guest tool keys found in the registry
+const CASES: &[(&[&str], &[&str], Option<Hypervisor>)] = &[
+    (&["Microsoft Corporation", "Virtual Machine", "VRTUAL - 12001807", "7783-7084-3265-9085-8269-3286-77"], &[], Some(Hypervisor::Azure)),
+    (&["Microsoft Corporation", "Virtual Machine", "VRTUAL - 1", "5215-1493-4720-6338-1571-0578-14"], &[], Some(Hypervisor::HyperV)),
+    (&["Amazon EC2", "t3.large", "Amazon EC2", "i-0123456789abcdef0"], &[], Some(Hypervisor::AWS)),
+    (&["Google", "Google Compute Engine", "Google", ""], &[], Some(Hypervisor::GCE)),
+    (&["VMware, Inc.", "VMware7,1", "INTEL  - 6040000", "No Asset Tag"], &[], Some(Hypervisor::VMware)),
+    (&["innotek GmbH", "VirtualBox", "VBOX   - 1", ""], &[], Some(Hypervisor::VirtualBox)),
+    (&["QEMU", "Standard PC (Q35 + ICH9, 2009)", "BOCHS  - 1", ""], &[], Some(Hypervisor::QEMU)),
+    (&["Red Hat", "KVM", "SeaBIOS", ""], &[], Some(Hypervisor::QEMU)),
+    (&["Xen", "HVM domU", "Xen - 0", ""], &[], Some(Hypervisor::Xen)),
+    (&["Parallels Software International Inc.", "Parallels Virtual Platform", "PRLS   - 1", ""], &[], Some(Hypervisor::Parallels)),
+    // older ec2 instances run on xen, and the cloud wins
+    (&["Xen", "HVM domU", "Xen - 0", "Amazon EC2"], &[], Some(Hypervisor::AWS)),
+    (&["Micro-Star International Co., Ltd.", "MS-7C56", "ALASKA - 1072009", "Default string"], &[], None),
+    // the vendor names alone are not enough, as they also build physical machines
+    (&["Google", "Eve", "Google_Eve.9584.174.0", ""], &[], None),
+    (&["Amazon", "Fire TV", "", ""], &[], None),
+    (&["Xenon Systems", "Workstation KVM-Ready", "American Megatrends Inc. - 5000B", "Box Kvmx"], &[], None),
+    (&["Microsoft Corporation", "Surface Laptop 5", "Hyper-V aware firmware", ""], &[], None),
+    (&[], &[], None)
+];
+
+#[test]
+fn hypervisors_are_classified_from_smbios_strings() {
+    for (smbios_strings, registry_markers, expected) in CASES {
+        assert_eq!(virtualization::ClassifyHypervisor(smbios_strings, registry_markers), *expected, "{:?}", smbios_strings);
+    }
+}
+
+#[test]
+fn registry_markers_alone_are_enough() {
+    let generic = ["System manufacturer", "System Product Name", "1.0", ""];
+
+    assert_eq!(virtualization::ClassifyHypervisor(&generic, &[r"SOFTWARE\VMware, Inc.\VMware Tools"]), Some(Hypervisor::VMware));
+    assert_eq!(virtualization::ClassifyHypervisor(&generic, &[r"software\citrix\xentools"]), Some(Hypervisor::Xen));
+    assert_eq!(virtualization::ClassifyHypervisor(&[], &[r"SYSTEM\CurrentControlSet\Services\QEMU-GA"]), Some(Hypervisor::QEMU));
+    assert_eq!(virtualization::ClassifyHypervisor(&generic, &[r"SOFTWARE\Unrelated"]), None);
+}
+
+#[test]
+fn clouds_win_over_the_hypervisor_they_run_on() {
+    let hyper_v = ["Microsoft Corporation", "Virtual Machine"];
+
+    assert_eq!(virtualization::ClassifyHypervisor(&hyper_v, &[r"SOFTWARE\Microsoft\Windows Azure"]), Some(Hypervisor::Azure));
+    assert_eq!(virtualization::ClassifyHypervisor(&hyper_v, &[]), Some(Hypervisor::HyperV));
+}
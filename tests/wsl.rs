@@ -0,0 +1,47 @@
+#![allow(non_snake_case)]
+
+use std::fs;
+
+use linux_commands_on_windows::winfetch::model::wsl::Wsl;
+
+// the `Lxss` key is read from a snapshot in the format of `--dump`, since the registry types are internal
+fn FromFixture() -> Wsl {
+    let content = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/lxss.json")).unwrap();
+
+    Wsl::FromLxssKey(&serde_json::from_str(&content).unwrap())
+}
+
+#[test]
+fn distributions_are_read_from_the_lxss_key() {
+    let wsl = FromFixture();
+    let names = wsl.Distributions.iter().map(|distribution| distribution.DistributionName.as_str()).collect::<Vec<_>>();
+
+    // subkeys without a `DistributionName` are left out
+    assert_eq!(names, ["Debian", "Ubuntu"]);
+}
+
+#[test]
+fn the_default_distribution_guid_is_matched_case_insensitively() {
+    let wsl = FromFixture();
+
+    assert!(!wsl.Distributions[0].Default);
+    assert!(wsl.Distributions[1].Default);
+}
+
+#[test]
+fn the_version_comes_from_the_vm_mode_flag() {
+    // both distributions have a `Version` of 2, which is the registration format
+    let wsl = FromFixture();
+
+    assert_eq!(wsl.Distributions[0].Version, 1);
+    assert_eq!(wsl.Distributions[1].Version, 2);
+}
+
+#[test]
+fn base_paths_are_shown_as_mnt_paths() {
+    let wsl = FromFixture();
+
+    assert_eq!(wsl.Distributions[0].BasePath, "/mnt/d/WSL/Debian");
+    assert_eq!(wsl.Distributions[1].BasePath, "/mnt/c/Users/alex/AppData/Local/Packages/Ubuntu/LocalState");
+    assert_eq!(wsl.to_string(), "(Debian) WSL 1, /mnt/d/WSL/Debian, (Ubuntu) WSL 2, default, /mnt/c/Users/alex/AppData/Local/Packages/Ubuntu/LocalState");
+}